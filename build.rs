@@ -1,20 +1,165 @@
-use std::{env, path::Path};
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let out_dir = env::var("OUT_DIR")?;
     let out_path = Path::new(&out_dir);
+    let mut diagnostics = Diagnostics::default();
 
-    println!("cargo:rerun-if-changed=dimensions.txt");
-    dimensions::generate(&out_path.join("dimensions_generated.rs"))?;
+    let sources = read_sources(
+        "dimensions.txt",
+        "DANWI_DIMENSIONS",
+        "DANWI_EXTRA_DIMENSIONS",
+    )?;
+    let dimensions = dimensions::generate(
+        &sources,
+        &out_path.join("dimensions_generated.rs"),
+        &mut diagnostics,
+    )?;
 
-    println!("cargo:rerun-if-changed=units.txt");
-    units::generate(&out_path.join("units_generated.rs"))?;
+    let sources = read_sources("units.txt", "DANWI_UNITS", "DANWI_EXTRA_UNITS")?;
+    units::generate(
+        &sources,
+        &dimensions,
+        &out_path.join("units_generated.rs"),
+        &mut diagnostics,
+    )?;
 
+    // the errors have been logged, which makes Cargo fail the build
     Ok(())
 }
 
 type Error = Box<dyn std::error::Error>;
 
+/// Sections of the definition files that are only generated with the cargo
+/// feature of the same name.
+const CATEGORIES: &[&str] = &[
+    "kinematic",
+    "mechanical",
+    "electrical",
+    "magnetic",
+    "fluid",
+    "information",
+    "photometric",
+    "radiological",
+    "chemical",
+    "thermal",
+];
+
+/// Returns whether a cargo feature (e.g. a category) is enabled.
+fn is_enabled(feature: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
+}
+
+/// A definition file.
+struct Source {
+    path: PathBuf,
+    content: String,
+}
+
+impl Source {
+    /// Returns the lines that are neither empty nor comments, with their
+    /// locations, skipping the sections of disabled categories.
+    fn lines(&self) -> impl Iterator<Item = (Location, &str)> {
+        let mut enabled = true;
+
+        self.content
+            .lines()
+            .enumerate()
+            .filter_map(move |(i, line)| {
+                let line = line.trim();
+
+                // a comment starts a section, which lasts until the next one
+                if let Some(heading) = line.strip_prefix('#') {
+                    let heading = heading.trim();
+                    enabled = !CATEGORIES.contains(&heading) || is_enabled(heading);
+                    return None;
+                }
+
+                let location = Location {
+                    path: self.path.clone(),
+                    line: i + 1,
+                };
+                (enabled && !line.is_empty()).then_some((location, line))
+            })
+    }
+}
+
+/// Position of a definition, used in error messages.
+#[derive(Debug, Clone)]
+struct Location {
+    path: PathBuf,
+    line: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)
+    }
+}
+
+/// Problems found in the definition files.
+///
+/// They are reported to Cargo as they are found, so that a build lists all of
+/// them rather than stopping at the first one. Cargo fails the build if any
+/// of them is an error.
+#[derive(Default)]
+struct Diagnostics {
+    errors: usize,
+}
+
+impl Diagnostics {
+    fn warning(&self, message: impl fmt::Display) {
+        println!("cargo::warning={}", message);
+    }
+
+    fn error(&mut self, message: impl fmt::Display) {
+        println!("cargo::error={}", message);
+        self.errors += 1;
+    }
+}
+
+/// Reads the built-in definition file, or the file named by the environment
+/// variable `replace`, followed by the files listed in the environment variable
+/// `extra` (separated like `PATH`).
+fn read_sources(builtin: &str, replace: &str, extra: &str) -> Result<Vec<Source>, Error> {
+    println!("cargo::rerun-if-env-changed={}", replace);
+    println!("cargo::rerun-if-env-changed={}", extra);
+
+    let main = env::var_os(replace).map_or_else(|| PathBuf::from(builtin), PathBuf::from);
+    let extras = env::var_os(extra)
+        .map(|paths| env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    std::iter::once(main)
+        .chain(extras)
+        .filter(|path| !path.as_os_str().is_empty())
+        .map(|path| {
+            println!("cargo::rerun-if-changed={}", path.display());
+            let content = fs::read_to_string(&path)
+                .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+            Ok(Source { path, content })
+        })
+        .collect()
+}
+
+/// Writes the header of a generated file.
+fn write_header(code: &mut String, sources: &[Source]) -> fmt::Result {
+    use fmt::Write;
+
+    writeln!(code, "// This file is automatically generated by build.rs")?;
+    writeln!(code, "// Do not edit manually!")?;
+
+    for source in sources {
+        writeln!(code, "// Source: {}", source.path.display())?;
+    }
+
+    writeln!(code)
+}
+
 fn to_pascal_case(s: &str) -> String {
     s.split(|c: char| c == '_' || c == '-' || c.is_whitespace())
         .filter(|word| !word.is_empty())
@@ -35,97 +180,286 @@ fn to_pascal_case(s: &str) -> String {
 
 mod dimensions {
     use super::*;
-    use std::{fmt::Write, fs, path::PathBuf};
+    use std::fmt::Write;
+
+    /// Dimensions that danwi itself refers to, which replacement tables must
+    /// define, with the category that needs them.
+    const REQUIRED: &[(&str, Option<&str>)] = &[
+        ("dimensionless", None),
+        ("time", None),
+        ("length", None),
+        ("mass", None),
+        ("electric_current", None),
+        ("thermodynamic_temperature", None),
+        ("amount_of_substance", None),
+        ("luminous_intensity", None),
+        // `G_0`
+        ("acceleration", Some("kinematic")),
+    ];
+
+    /// Symbols of the base dimensions, in the order of the exponents.
+    const SYMBOLS: [&str; 8] = ["T", "L", "M", "I", "K", "N", "J", "X1"];
+
+    /// Order of the base dimensions in formulas (e.g. `M·L·T⁻²`).
+    const FORMULA_ORDER: [usize; 8] = [2, 1, 0, 3, 4, 5, 6, 7];
+
+    const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
 
     #[derive(Debug, Clone)]
     struct Dimension {
         name: String,
-        exponents: [i8; 7],
+        /// Exponents of the SI base dimensions and of information (X1).
+        exponents: [i8; 8],
+        /// Whether the dimension is a kind of quantity sharing its exponents
+        /// with another one (e.g. activity and frequency).
+        kind: bool,
         doc: Option<String>,
+        location: Location,
     }
 
-    pub fn generate(output_path: &PathBuf) -> Result<(), Error> {
-        let content = fs::read_to_string("dimensions.txt")?;
-        let dimensions = parse_dimensions(&content)?;
-        validate_dimensions(&dimensions)?;
-        let code = generate_code(&dimensions)?;
+    /// Generates the dimension aliases, returning the names of the dimensions
+    /// and whether each is a kind.
+    pub fn generate(
+        sources: &[Source],
+        output_path: &PathBuf,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<(String, bool)>, Error> {
+        let dimensions = merge_dimensions(parse_dimensions(sources, diagnostics), diagnostics);
+        validate_dimensions(sources, &dimensions, diagnostics);
+        let code = generate_code(sources, &dimensions)?;
         fs::write(output_path, code)?;
-        Ok(())
+        Ok(dimensions
+            .into_iter()
+            .map(|dim| (dim.name, dim.kind))
+            .collect())
     }
 
-    fn parse_dimensions(content: &str) -> Result<Vec<Dimension>, Error> {
+    fn parse_dimensions(sources: &[Source], diagnostics: &mut Diagnostics) -> Vec<Dimension> {
         let mut dimensions = Vec::new();
-        let mut line_num = 0;
-
-        for line in content.lines() {
-            line_num += 1;
-            let trimmed_line = line.trim();
 
-            if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
-                continue;
+        for (location, line) in sources.iter().flat_map(Source::lines) {
+            match parse_dimension_line(line, location.clone()) {
+                Ok(dim) => dimensions.push(dim),
+                Err(err) => diagnostics.error(format_args!("{}: {}: {}", location, err, line)),
             }
-
-            let Some(dim) = parse_dimension_line(trimmed_line) else {
-                panic!("invalid dimension on line {}: {}", line_num, trimmed_line);
-            };
-
-            dimensions.push(dim);
         }
 
-        assert!(!dimensions.is_empty());
-        Ok(dimensions)
+        dimensions
     }
 
-    fn parse_dimension_line(line: &str) -> Option<Dimension> {
+    fn parse_dimension_line(line: &str, location: Location) -> Result<Dimension, String> {
         // Split by #
         let parts: Vec<&str> = line.splitn(2, '#').collect();
         let definition = parts[0].trim();
         let doc = parts.get(1).map(|s| s.trim().to_string());
 
         // Parse name and exponents
-        let mut parts = definition.splitn(2, ':');
-        let name = parts.next()?.trim().to_string();
-        let exponents = parts.next()?.trim();
+        let Some((name, exponents)) = definition.split_once(':') else {
+            return Err("expected `name: exponents`".into());
+        };
+
+        let mut exponents: Vec<&str> = exponents.split_whitespace().collect();
+        let kind = exponents.last() == Some(&"kind");
+        if kind {
+            exponents.pop();
+        }
 
         // Parse exponents
-        let exponents: Vec<i8> = exponents
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
+        let exponents = exponents
+            .into_iter()
+            .map(|s| {
+                let exp: i64 = s.parse().map_err(|_| format!("invalid exponent `{}`", s))?;
+                i8::try_from(exp).map_err(|_| {
+                    format!(
+                        "exponent {} is out of range ({}..={})",
+                        exp,
+                        i8::MIN,
+                        i8::MAX
+                    )
+                })
+            })
+            .collect::<Result<Vec<i8>, String>>()?;
 
-        if exponents.len() != 7 {
-            return None;
-        }
+        // the exponent of information is optional
+        let exponents = match *exponents.as_slice() {
+            [t, l, m, i, k, n, j] => [t, l, m, i, k, n, j, 0],
+            [t, l, m, i, k, n, j, x1] => [t, l, m, i, k, n, j, x1],
+            _ => return Err("expected 7 exponents (T L M I K N J) and optionally X1".into()),
+        };
 
-        Some(Dimension {
-            name,
-            exponents: exponents.try_into().ok()?,
+        Ok(Dimension {
+            name: name.trim().to_string(),
+            exponents,
+            kind,
             doc,
+            location,
         })
     }
 
-    fn validate_dimensions(dimensions: &[Dimension]) -> Result<(), Error> {
-        let mut seen = std::collections::HashSet::new();
+    /// Merges the dimensions of all sources, skipping identical redefinitions
+    /// in later files.
+    fn merge_dimensions(
+        dimensions: Vec<Dimension>,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Dimension> {
+        let mut merged: Vec<Dimension> = Vec::new();
 
         for dim in dimensions {
-            if !seen.insert(&dim.name) {
-                panic!("duplicate dimension name: {}", dim.name);
+            match merged.iter().find(|other| other.name == dim.name) {
+                None => merged.push(dim),
+                Some(other)
+                    if other.location.path != dim.location.path
+                        && other.exponents == dim.exponents
+                        && other.kind == dim.kind => {}
+                Some(other) if other.location.path == dim.location.path => {
+                    diagnostics.error(format_args!(
+                        "{}: duplicate dimension `{}`, first defined at {}",
+                        dim.location, dim.name, other.location
+                    ))
+                }
+                Some(other) => diagnostics.error(format_args!(
+                    "{}: conflicting definition of dimension `{}`, already defined at {}",
+                    dim.location, dim.name, other.location
+                )),
             }
         }
 
-        Ok(())
+        merged
     }
 
-    fn generate_code(dimensions: &[Dimension]) -> Result<String, Error> {
+    fn validate_dimensions(
+        sources: &[Source],
+        dimensions: &[Dimension],
+        diagnostics: &mut Diagnostics,
+    ) {
+        let path = sources.first().map_or_else(
+            || "dimensions".into(),
+            |source| source.path.display().to_string(),
+        );
+
+        for &(name, category) in REQUIRED {
+            if category.is_none_or(is_enabled) && !dimensions.iter().any(|dim| dim.name == name) {
+                diagnostics.error(format_args!(
+                    "{}: missing dimension `{}`, which danwi requires",
+                    path, name
+                ));
+            }
+        }
+
+        // dimensions are named by their exponents, which must be unique but
+        // for kinds
+        for (i, dim) in dimensions.iter().enumerate() {
+            let other = dimensions[..i]
+                .iter()
+                .find(|other| other.exponents == dim.exponents && !other.kind);
+
+            if let Some(other) = other
+                && !dim.kind
+            {
+                diagnostics.warning(format_args!(
+                    "{}: `{}` has the exponents of `{}` ({}), one of them should be a `kind`",
+                    dim.location, dim.name, other.name, other.location
+                ));
+            }
+        }
+
+        for dim in dimensions {
+            let Some(formula) = dim.doc.as_deref().and_then(doc_formula) else {
+                continue;
+            };
+
+            match parse_formula(formula) {
+                Some(exponents) if exponents == dim.exponents => {}
+                Some(_) => diagnostics.warning(format_args!(
+                    "{}: the doc of `{}` gives `{}` but its exponents are `{}`",
+                    dim.location,
+                    dim.name,
+                    formula,
+                    to_formula(&dim.exponents)
+                )),
+                None => diagnostics.warning(format_args!(
+                    "{}: cannot read the formula `{}` in the doc of `{}`",
+                    dim.location, formula, dim.name
+                )),
+            }
+        }
+    }
+
+    /// Returns the formula in a doc such as `Force (newton, N) = M·L·T⁻² =
+    /// kg·m/s²`, the first one after the name of the dimension.
+    fn doc_formula(doc: &str) -> Option<&str> {
+        doc.split(" = ").nth(1).map(str::trim)
+    }
+
+    /// Parses a formula of base dimensions such as `M·L²·T⁻³·I⁻¹`.
+    fn parse_formula(formula: &str) -> Option<[i8; 8]> {
+        let mut exponents = [0; 8];
+
+        if formula == "1" {
+            return Some(exponents);
+        }
+
+        for factor in formula.split('·') {
+            let index = SYMBOLS.iter().position(|s| factor.starts_with(s))?;
+
+            let power: String = factor[SYMBOLS[index].len()..]
+                .chars()
+                .map(|c| match c {
+                    '⁻' => Some('-'),
+                    c => SUPERSCRIPTS
+                        .iter()
+                        .position(|&d| d == c)
+                        .and_then(|i| char::from_digit(i as u32, 10)),
+                })
+                .collect::<Option<_>>()?;
+
+            exponents[index] += if power.is_empty() {
+                1
+            } else {
+                power.parse().ok()?
+            };
+        }
+
+        Some(exponents)
+    }
+
+    /// Formats exponents as a formula such as `M·L²·T⁻³·I⁻¹`.
+    fn to_formula(exponents: &[i8; 8]) -> String {
+        let factors: Vec<String> = FORMULA_ORDER
+            .iter()
+            .filter(|&&i| exponents[i] != 0)
+            .map(|&i| {
+                let mut factor = SYMBOLS[i].to_string();
+
+                if exponents[i] < 0 {
+                    factor.push('⁻');
+                }
+
+                if exponents[i] != 1 {
+                    factor.extend(
+                        exponents[i]
+                            .unsigned_abs()
+                            .to_string()
+                            .chars()
+                            .map(|d| SUPERSCRIPTS[d.to_digit(10).unwrap() as usize]),
+                    );
+                }
+
+                factor
+            })
+            .collect();
+
+        if factors.is_empty() {
+            "1".into()
+        } else {
+            factors.join("·")
+        }
+    }
+
+    fn generate_code(sources: &[Source], dimensions: &[Dimension]) -> Result<String, Error> {
         let mut code = String::new();
 
-        writeln!(
-            &mut code,
-            "// This file is automatically generated by build.rs"
-        )?;
-        writeln!(&mut code, "// Do not edit manually!")?;
-        writeln!(&mut code, "// Source: dimensions.txt")?;
-        writeln!(&mut code)?;
+        write_header(&mut code, sources)?;
         writeln!(&mut code, "use typenum::*;")?;
         writeln!(&mut code)?;
 
@@ -134,102 +468,840 @@ mod dimensions {
                 writeln!(code, "/// {}", doc)?;
             }
 
-            write!(code, "pub type {} = Dimension<", to_pascal_case(&dim.name))?;
-
-            for (i, &exp) in dim.exponents.iter().enumerate() {
-                if i > 0 {
-                    write!(code, ", ")?;
-                }
+            let (exponents, information) = dim.exponents.split_at(7);
+            let mut params: Vec<String> = exponents.iter().map(|&exp| integer(exp)).collect();
 
-                write!(
-                    code,
-                    "{}",
-                    match exp {
-                        -4 => "N4",
-                        -3 => "N3",
-                        -2 => "N2",
-                        -1 => "N1",
-                        0 => "Z0",
-                        1 => "P1",
-                        2 => "P2",
-                        3 => "P3",
-                        4 => "P4",
-                        _ => unreachable!(),
-                    }
-                )?;
+            if information[0] != 0 {
+                params.push(format!("TArr<{}, ATerm>", integer(information[0])));
             }
 
-            writeln!(code, ">;")?;
+            writeln!(
+                code,
+                "pub type {} = Dimension<{}>;",
+                to_pascal_case(&dim.name),
+                params.join(", ")
+            )?;
             writeln!(code)?;
         }
 
+        writeln!(
+            code,
+            "/// Names and exponents of the dimensions in the definition files."
+        )?;
+        writeln!(
+            code,
+            "const NAMED_DIMENSIONS: &[(&str, [i8; 7], &[i8])] = &["
+        )?;
+
+        let named = named_dimensions(dimensions);
+        for dim in &named {
+            let (exponents, information) = dim.exponents.split_at(7);
+            let extra = if information[0] == 0 {
+                &[][..]
+            } else {
+                information
+            };
+
+            writeln!(
+                code,
+                "    (\"{}\", {:?}, &{:?}),",
+                to_pascal_case(&dim.name),
+                exponents,
+                extra
+            )?;
+        }
+
+        writeln!(code, "];")?;
+        writeln!(code)?;
+
+        // marker types and a search tree for the names in compile errors
+        let mut named = named;
+        named.sort_by_key(|dim| dim.exponents);
+
+        writeln!(
+            code,
+            "/// Marker types naming the dimensions in compile errors (see"
+        )?;
+        writeln!(code, "/// [`DimensionName`]).")?;
+        writeln!(code, "pub mod names {{")?;
+        for dim in &named {
+            let name = to_pascal_case(&dim.name);
+            writeln!(
+                code,
+                "    /// Name of [`{0}`](super::{0}) in compile errors.",
+                name
+            )?;
+            writeln!(code, "    pub enum {} {{}}", name)?;
+        }
+        writeln!(code, "}}")?;
+        writeln!(code)?;
+
+        writeln!(
+            code,
+            "/// The named dimensions as a binary search tree ordered by their exponents."
+        )?;
+        writeln!(code, "#[doc(hidden)]")?;
+        writeln!(code, "pub type NameTree = {};", name_tree(&named))?;
+
         Ok(code)
     }
+
+    /// Returns the dimensions that name their exponents: those without a kind,
+    /// and the first kind of exponents that only have kinds (e.g. frequency,
+    /// shared with activity).
+    fn named_dimensions(dimensions: &[Dimension]) -> Vec<&Dimension> {
+        dimensions
+            .iter()
+            .enumerate()
+            .filter(|&(i, dim)| {
+                let shared = |other: &Dimension| other.exponents == dim.exponents;
+                let first = !dimensions[..i].iter().any(shared);
+                !dim.kind || first && !dimensions.iter().any(|other| shared(other) && !other.kind)
+            })
+            .map(|(_, dim)| dim)
+            .collect()
+    }
+
+    /// Returns a balanced tree of `NameNode`s of dimensions sorted by their
+    /// exponents.
+    fn name_tree(dimensions: &[&Dimension]) -> String {
+        if dimensions.is_empty() {
+            return "()".into();
+        }
+
+        let mid = dimensions.len() / 2;
+        let name = to_pascal_case(&dimensions[mid].name);
+        format!(
+            "NameNode<{0}, names::{0}, {1}, {2}>",
+            name,
+            name_tree(&dimensions[..mid]),
+            name_tree(&dimensions[mid + 1..])
+        )
+    }
+
+    /// Returns the `typenum` integer of an exponent (e.g. `N2`).
+    fn integer(exp: i8) -> String {
+        match exp {
+            0 => "Z0".into(),
+            exp if exp < 0 => format!("N{}", exp.unsigned_abs()),
+            exp => format!("P{}", exp),
+        }
+    }
 }
 
 mod units {
     use super::*;
-    use std::{fmt::Write, fs, path::PathBuf};
+    use std::fmt::Write;
 
     #[derive(Debug, Clone)]
     struct Unit {
         name: String,
         symbol: String,
+        /// Plurals and other spellings of the name.
+        names: Vec<String>,
+        /// Alternative symbols (e.g. `Ω`).
+        symbols: Vec<String>,
         dimension: String,
+        /// Power of ten relative to the coherent unit.
+        scale: i8,
+        /// Factor besides the power of ten, as a float literal.
+        factor: Option<String>,
+        /// Value of the zero in coherent units, as a float literal.
+        offset: Option<String>,
+        /// Prefix policy (e.g. `common + binary`), the default one if omitted.
+        prefixes: Option<String>,
+        /// Symbol shown in results (e.g. `m/s`), the symbol if omitted.
+        display: Option<String>,
+        /// Columns after the symbol, compared when merging files.
+        definition: String,
+        location: Location,
     }
 
-    pub fn generate(output_path: &PathBuf) -> Result<(), Error> {
-        let content = fs::read_to_string("units.txt")?;
-        let units = parse_units(&content)?;
-        let code = generate_code(&units)?;
+    /// A unit whose prefixes are raised to a power (e.g. `cm2` is (10⁻² m)²).
+    #[derive(Debug, Clone)]
+    struct PoweredUnit {
+        name: String,
+        symbol: String,
+        base: String,
+        exponent: i8,
+        location: Location,
+    }
+
+    /// A line of the units file.
+    enum Definition {
+        Unit(Unit),
+        Powered(PoweredUnit),
+    }
+
+    /// Symbols, names and powers of ten of the prefixes in `prefix::ALL`.
+    const PREFIXES: &[(&str, &str, i8)] = &[
+        ("Q", "quetta", 30),
+        ("R", "ronna", 27),
+        ("Y", "yotta", 24),
+        ("Z", "zetta", 21),
+        ("E", "exa", 18),
+        ("P", "peta", 15),
+        ("T", "tera", 12),
+        ("G", "giga", 9),
+        ("M", "mega", 6),
+        ("k", "kilo", 3),
+        ("h", "hecto", 2),
+        ("da", "deca", 1),
+        ("d", "deci", -1),
+        ("c", "centi", -2),
+        ("m", "milli", -3),
+        ("u", "micro", -6),
+        ("n", "nano", -9),
+        ("p", "pico", -12),
+        ("f", "femto", -15),
+        ("a", "atto", -18),
+        ("z", "zepto", -21),
+        ("y", "yocto", -24),
+        ("r", "ronto", -27),
+        ("q", "quecto", -30),
+    ];
+
+    /// Keywords of Rust, which cannot be used as constants or methods.
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+        "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box",
+        "do", "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+    ];
+
+    pub fn generate(
+        sources: &[Source],
+        dimensions: &[(String, bool)],
+        output_path: &PathBuf,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(), Error> {
+        let (units, powered) = parse_units(sources, diagnostics);
+        let (units, powered) = merge_units(units, powered, diagnostics);
+        validate_units(&units, &powered, dimensions, diagnostics);
+        let code = generate_code(sources, dimensions, &units, &powered)?;
         fs::write(output_path, code)?;
         Ok(())
     }
 
-    fn parse_units(content: &str) -> Result<Vec<Unit>, Error> {
+    fn parse_units(
+        sources: &[Source],
+        diagnostics: &mut Diagnostics,
+    ) -> (Vec<Unit>, Vec<PoweredUnit>) {
         let mut units = Vec::new();
+        let mut powered = Vec::new();
 
-        for line in content.lines() {
-            let trimmed_line = line.trim();
-
-            if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
-                continue;
+        for (location, line) in sources.iter().flat_map(Source::lines) {
+            match parse_unit_line(line, location.clone()) {
+                Ok(Definition::Unit(unit)) => units.push(unit),
+                Ok(Definition::Powered(unit)) => powered.push(unit),
+                Err(err) => diagnostics.error(format_args!("{}: {}: {}", location, err, line)),
             }
+        }
 
-            let parts: Vec<&str> = trimmed_line.split_whitespace().collect();
+        (units, powered)
+    }
 
-            assert_eq!(parts.len(), 3);
+    fn parse_unit_line(line: &str, location: Location) -> Result<Definition, String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
 
-            units.push(Unit {
+        if parts.len() < 3 {
+            return Err("expected `name symbol dimension [key=value...]`".into());
+        }
+
+        if let Some((base, exponent)) = parts[2].split_once('^') {
+            if parts.len() > 3 {
+                return Err("powered units have no options".into());
+            }
+
+            return Ok(Definition::Powered(PoweredUnit {
                 name: parts[0].into(),
                 symbol: parts[1].into(),
-                dimension: parts[2].into(),
-            });
+                base: base.into(),
+                exponent: {
+                    let exp: i64 = exponent
+                        .parse()
+                        .map_err(|_| format!("invalid exponent `{}`", exponent))?;
+                    i8::try_from(exp).map_err(|_| {
+                        format!(
+                            "exponent {} is out of range ({}..={})",
+                            exp,
+                            i8::MIN,
+                            i8::MAX
+                        )
+                    })?
+                },
+                location,
+            }));
+        }
+
+        let mut unit = Unit {
+            name: parts[0].into(),
+            symbol: parts[1].into(),
+            names: Vec::new(),
+            symbols: Vec::new(),
+            dimension: parts[2].into(),
+            scale: 0,
+            factor: None,
+            offset: None,
+            prefixes: None,
+            display: None,
+            definition: parts[2..].join(" "),
+            location,
+        };
+
+        for option in &parts[3..] {
+            let Some((key, value)) = option.split_once('=') else {
+                return Err(format!("expected `key=value`, found `{}`", option));
+            };
+
+            let list = || value.split(',').map(String::from);
+
+            match key {
+                "scale" => {
+                    let scale: f64 = value
+                        .parse()
+                        .map_err(|_| format!("invalid scale `{}`", value))?;
+                    if !scale.is_finite() || scale <= 0.0 {
+                        return Err(format!("scale `{}` is not a positive finite number", value));
+                    }
+                    let exp = scale.log10().round();
+
+                    // exact powers of ten become part of the prefix, whose
+                    // exponent is an `i8`
+                    if format!("1e{}", exp).parse() == Ok(scale) {
+                        if exp < i8::MIN as f64 || exp > i8::MAX as f64 {
+                            return Err(format!(
+                                "scale `{}` is out of range (1e-128 to 1e127)",
+                                value
+                            ));
+                        }
+                        unit.scale = exp as i8;
+                    } else {
+                        unit.factor = Some(format!("{:?}", scale));
+                    }
+                }
+                "offset" => {
+                    let offset: f64 = value
+                        .parse()
+                        .map_err(|_| format!("invalid offset `{}`", value))?;
+                    unit.offset = Some(format!("{:?}", offset));
+                }
+                // a plural may be the name itself (e.g. `siemens`)
+                "plural" => unit
+                    .names
+                    .extend(list().filter(|plural| *plural != unit.name)),
+                "names" => unit.names.extend(list()),
+                "symbols" => unit.symbols.extend(list()),
+                "display" => unit.display = Some(value.into()),
+                "prefixes" if is_policy(value) => {
+                    unit.prefixes = Some(value.replace('+', " + "));
+                }
+                "prefixes" => {
+                    return Err(format!(
+                        "unknown prefixes `{}`, expected `all`, `common`, `none` or `default`, \
+                         optionally followed by `+binary`, or `binary`",
+                        value
+                    ));
+                }
+                _ => return Err(format!("unknown option `{}`", key)),
+            }
+        }
+
+        Ok(Definition::Unit(unit))
+    }
+
+    /// Names, symbols and definition of a unit, used to detect conflicts.
+    struct Entry {
+        names: Vec<String>,
+        symbols: Vec<String>,
+        definition: String,
+        location: Location,
+    }
+
+    /// Merges the units of all sources, skipping identical redefinitions in
+    /// later files.
+    fn merge_units(
+        units: Vec<Unit>,
+        powered: Vec<PoweredUnit>,
+        diagnostics: &mut Diagnostics,
+    ) -> (Vec<Unit>, Vec<PoweredUnit>) {
+        let mut entries = Vec::new();
+
+        let units = units
+            .into_iter()
+            .filter(|unit| {
+                let entry = Entry {
+                    names: [&unit.name]
+                        .into_iter()
+                        .chain(&unit.names)
+                        .cloned()
+                        .collect(),
+                    symbols: [&unit.symbol]
+                        .into_iter()
+                        .chain(&unit.symbols)
+                        .cloned()
+                        .collect(),
+                    definition: unit.definition.clone(),
+                    location: unit.location.clone(),
+                };
+                insert(&mut entries, entry, diagnostics)
+            })
+            .collect();
+
+        let powered = powered
+            .into_iter()
+            .filter(|unit| {
+                let entry = Entry {
+                    names: vec![unit.name.clone()],
+                    symbols: vec![unit.symbol.clone()],
+                    definition: format!("{}^{}", unit.base, unit.exponent),
+                    location: unit.location.clone(),
+                };
+                insert(&mut entries, entry, diagnostics)
+            })
+            .collect();
+
+        (units, powered)
+    }
+
+    /// Records a unit, returning `false` if it repeats a definition from an
+    /// earlier file or conflicts with one.
+    fn insert(entries: &mut Vec<Entry>, entry: Entry, diagnostics: &mut Diagnostics) -> bool {
+        let other = entries.iter().find(|other| {
+            other.names.iter().any(|name| entry.names.contains(name))
+                || other
+                    .symbols
+                    .iter()
+                    .any(|symbol| entry.symbols.contains(symbol))
+        });
+
+        let (name, symbol) = (&entry.names[0], &entry.symbols[0]);
+
+        match other {
+            None => {
+                entries.push(entry);
+                true
+            }
+            Some(other) if other.location.path == entry.location.path => {
+                diagnostics.error(format_args!(
+                    "{}: duplicate unit `{}` ({}), first defined as `{}` ({}) at {}",
+                    entry.location, name, symbol, other.names[0], other.symbols[0], other.location
+                ));
+                false
+            }
+            Some(other)
+                if other.names == entry.names
+                    && other.symbols == entry.symbols
+                    && other.definition == entry.definition =>
+            {
+                false
+            }
+            Some(other) => {
+                diagnostics.error(format_args!(
+                    "{}: conflicting definition of unit `{}` ({}), already defined as `{}` ({}) \
+                     at {}",
+                    entry.location, name, symbol, other.names[0], other.symbols[0], other.location
+                ));
+                false
+            }
+        }
+    }
+
+    /// Symbols of the prefixes in `prefix::COMMON`.
+    const COMMON: &[&str] = &["G", "M", "k", "m", "u", "n", "p"];
+
+    /// Symbols and names of the prefixes in `prefix::BINARY`.
+    const BINARY: &[(&str, &str)] = &[
+        ("Ei", "exbi"),
+        ("Pi", "pebi"),
+        ("Ti", "tebi"),
+        ("Gi", "gibi"),
+        ("Mi", "mebi"),
+        ("Ki", "kibi"),
+    ];
+
+    /// Returns whether a value of the `prefixes` option is a prefix policy,
+    /// such as `common` or `default+binary`.
+    fn is_policy(value: &str) -> bool {
+        match value.split_once('+') {
+            None => matches!(value, "all" | "common" | "none" | "default" | "binary"),
+            Some((decimal, "binary")) => matches!(decimal, "all" | "common" | "none" | "default"),
+            Some(_) => false,
+        }
+    }
+
+    /// Returns the prefixes of a prefix policy, whose decimal ones default to
+    /// those selected by the `prefixes-all` and `prefixes-common` features.
+    fn prefixes(policy: Option<&str>) -> Vec<(&'static str, &'static str)> {
+        let policy = policy.unwrap_or("default");
+        let binary = policy.ends_with("binary");
+
+        let (decimal, _) = policy.split_once(" + ").unwrap_or((policy, ""));
+        let decimal = match decimal {
+            "default" if is_enabled("prefixes-all") => "all",
+            "default" if is_enabled("prefixes-common") => "common",
+            decimal => decimal,
+        };
+
+        PREFIXES
+            .iter()
+            .filter(|(symbol, _, _)| match decimal {
+                "all" => true,
+                "common" => COMMON.contains(symbol),
+                _ => false,
+            })
+            .map(|&(symbol, name, _)| (symbol, name))
+            .chain(BINARY.iter().copied().filter(|_| binary))
+            .collect()
+    }
+
+    /// Returns the power of ten of a prefix, which is 0 for binary prefixes.
+    fn prefix_power(symbol: &str) -> i8 {
+        PREFIXES
+            .iter()
+            .find(|&&(prefix, _, _)| prefix == symbol)
+            .map_or(0, |&(_, _, power)| power)
+    }
+
+    /// Returns the identifiers `define_units!` uses for a prefix symbol, which
+    /// spells out `atto` since `as` is a keyword and adds `μ` for micro.
+    fn prefix_idents(symbol: &'static str) -> Vec<&'static str> {
+        match symbol {
+            "a" => vec!["atto"],
+            "u" => vec!["u", "μ"],
+            symbol => vec![symbol],
+        }
+    }
+
+    fn is_identifier(s: &str) -> bool {
+        let mut chars = s.chars();
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    }
+
+    /// Identifiers generated by `define_units!` in one namespace, with the
+    /// units they come from.
+    #[derive(Default)]
+    struct Identifiers<'a> {
+        origins: Vec<(String, String, &'a Location)>,
+    }
+
+    impl<'a> Identifiers<'a> {
+        /// Records an identifier, reporting an error if it is a keyword or
+        /// already generated for another unit.
+        fn insert(
+            &mut self,
+            ident: String,
+            origin: String,
+            location: &'a Location,
+            diagnostics: &mut Diagnostics,
+        ) {
+            if KEYWORDS.contains(&ident.as_str()) {
+                diagnostics.error(format_args!(
+                    "{}: `{}` for {} is a Rust keyword",
+                    location, ident, origin
+                ));
+            } else if let Some((_, other, other_location)) =
+                self.origins.iter().find(|(other, ..)| *other == ident)
+            {
+                diagnostics.error(format_args!(
+                    "{}: `{}` for {} clashes with {} at {}",
+                    location, ident, origin, other, other_location
+                ));
+            } else {
+                self.origins.push((ident, origin, location));
+            }
         }
+    }
+
+    /// Checks that the units refer to defined dimensions and base units, and
+    /// that the constants, methods and type aliases generated for them are
+    /// valid and distinct.
+    fn validate_units(
+        units: &[Unit],
+        powered: &[PoweredUnit],
+        dimensions: &[(String, bool)],
+        diagnostics: &mut Diagnostics,
+    ) {
+        // constants share the namespace of the methods, as each has a method
+        let mut methods = Identifiers::default();
+        let mut types = Identifiers::default();
+
+        for unit in units {
+            if !dimensions.iter().any(|(name, _)| *name == unit.dimension) {
+                diagnostics.error(format_args!(
+                    "{}: unknown dimension `{}` of `{}`",
+                    unit.location, unit.dimension, unit.name
+                ));
+            }
+
+            let symbols = [&unit.symbol].into_iter().chain(&unit.symbols);
+            let names = [&unit.name].into_iter().chain(&unit.names);
+
+            for ident in symbols.clone().chain(names.clone()) {
+                if !is_identifier(ident) {
+                    diagnostics.error(format_args!(
+                        "{}: `{}` of `{}` is not a valid identifier",
+                        unit.location, ident, unit.name
+                    ));
+                }
+            }
+
+            for symbol in symbols {
+                methods.insert(
+                    symbol.clone(),
+                    format!("the symbol of `{}`", unit.name),
+                    &unit.location,
+                    diagnostics,
+                );
+
+                for (prefix, prefix_name) in prefixes(unit.prefixes.as_deref()) {
+                    for ident in prefix_idents(prefix) {
+                        methods.insert(
+                            format!("{}{}", ident, symbol),
+                            format!("`{}` + `{}`", prefix_name, unit.name),
+                            &unit.location,
+                            diagnostics,
+                        );
+                    }
+                }
+            }
+
+            for name in names {
+                let name = name.to_lowercase();
+                methods.insert(
+                    name.clone(),
+                    format!("the name of `{}`", unit.name),
+                    &unit.location,
+                    diagnostics,
+                );
+            }
+
+            types.insert(
+                to_pascal_case(&unit.name),
+                format!("`{}`", unit.name),
+                &unit.location,
+                diagnostics,
+            );
+        }
+
+        for unit in powered {
+            let Some(base) = units.iter().find(|base| base.symbol == unit.base) else {
+                diagnostics.error(format_args!(
+                    "{}: unknown base unit `{}` of `{}`",
+                    unit.location, unit.base, unit.name
+                ));
+                continue;
+            };
+
+            let (per, suffix) = match unit.exponent {
+                0 | 1 => {
+                    diagnostics.error(format_args!(
+                        "{}: exponent {} of `{}`, expected a power other than 0 and 1",
+                        unit.location, unit.exponent, unit.name
+                    ));
+                    continue;
+                }
+                -1 => ("per_", String::new()),
+                exp if exp < 0 => ("per_", exp.unsigned_abs().to_string()),
+                exp => ("", exp.to_string()),
+            };
+
+            // the prefix goes before the base unit, so the symbol must be made
+            // of an optional `per_`, the base unit and an optional exponent
+            let symbol = format!("{}{}{}", per, unit.base, suffix);
+            if unit.symbol != symbol {
+                diagnostics.error(format_args!(
+                    "{}: the symbol of `{}` must be `{}`, found `{}`",
+                    unit.location, unit.name, symbol, unit.symbol
+                ));
+                continue;
+            }
+
+            // the power of ten of the base unit and each of its prefixes is
+            // raised to the exponent, and must remain an `i8`
+            let out_of_range = [""]
+                .into_iter()
+                .chain(
+                    prefixes(base.prefixes.as_deref())
+                        .into_iter()
+                        .map(|(p, _)| p),
+                )
+                .find(|&prefix| {
+                    let power =
+                        (base.scale as i32 + prefix_power(prefix) as i32) * unit.exponent as i32;
+                    i8::try_from(power).is_err()
+                });
+
+            if let Some(prefix) = out_of_range {
+                diagnostics.error(format_args!(
+                    "{}: exponent {} of `{}` is out of range, `{}{}` would be scaled by more \
+                     than 1e-128 to 1e127",
+                    unit.location, unit.exponent, unit.name, prefix, unit.base
+                ));
+                continue;
+            }
+
+            methods.insert(
+                symbol,
+                format!("the symbol of `{}`", unit.name),
+                &unit.location,
+                diagnostics,
+            );
+
+            for (prefix, prefix_name) in prefixes(base.prefixes.as_deref()) {
+                for ident in prefix_idents(prefix) {
+                    methods.insert(
+                        format!("{}{}{}{}", per, ident, unit.base, suffix),
+                        format!("`{}` + `{}`", prefix_name, unit.name),
+                        &unit.location,
+                        diagnostics,
+                    );
+                }
+            }
+
+            types.insert(
+                to_pascal_case(&unit.name),
+                format!("`{}`", unit.name),
+                &unit.location,
+                diagnostics,
+            );
+        }
+
+        // symbols that read as a prefixed unit without being one are
+        // ambiguous in expressions, which resolve them to the unprefixed unit
+        for unit in units {
+            for symbol in [&unit.symbol].into_iter().chain(&unit.symbols) {
+                for (prefix, prefix_name) in PREFIXES
+                    .iter()
+                    .map(|&(symbol, name, _)| (symbol, name))
+                    .chain(BINARY.iter().copied())
+                {
+                    let Some(rest) = symbol.strip_prefix(prefix) else {
+                        continue;
+                    };
 
-        Ok(units)
+                    let other = units.iter().find(|other| {
+                        [&other.symbol]
+                            .into_iter()
+                            .chain(&other.symbols)
+                            .any(|s| s == rest)
+                    });
+
+                    if let Some(other) = other
+                        && !prefixes(other.prefixes.as_deref())
+                            .iter()
+                            .any(|&(p, _)| p == prefix)
+                    {
+                        diagnostics.warning(format_args!(
+                            "{}: `{}` of `{}` also reads as `{}` + `{}` ({}), expressions will \
+                             resolve it to `{}`",
+                            unit.location,
+                            symbol,
+                            unit.name,
+                            prefix_name,
+                            other.name,
+                            other.location,
+                            unit.name
+                        ));
+                    }
+                }
+            }
+        }
     }
 
-    fn generate_code(units: &[Unit]) -> Result<String, Error> {
+    fn generate_code(
+        sources: &[Source],
+        dimensions: &[(String, bool)],
+        units: &[Unit],
+        powered: &[PoweredUnit],
+    ) -> Result<String, Error> {
         let mut code = String::new();
 
-        writeln!(
-            &mut code,
-            "// This file is automatically generated by build.rs"
-        )?;
-        writeln!(&mut code, "// Do not edit manually!")?;
-        writeln!(&mut code, "// Source: units.txt")?;
-        writeln!(&mut code)?;
+        write_header(&mut code, sources)?;
         writeln!(&mut code, "define_units! {{")?;
 
         for unit in units {
-            let dimension_type = to_pascal_case(&unit.dimension);
+            write!(&mut code, "    {}", unit.name)?;
+            for name in &unit.names {
+                write!(&mut code, " | {}", name)?;
+            }
 
-            writeln!(
+            write!(&mut code, " ({}", unit.symbol)?;
+            for symbol in &unit.symbols {
+                write!(&mut code, " | {}", symbol)?;
+            }
+
+            write!(&mut code, "): {}", to_pascal_case(&unit.dimension))?;
+
+            let options: Vec<String> = [
+                (unit.scale != 0).then(|| format!("scale: {}", unit.scale)),
+                unit.factor
+                    .as_ref()
+                    .map(|factor| format!("factor: {}", factor)),
+                unit.offset
+                    .as_ref()
+                    .map(|offset| format!("offset: {}", offset)),
+                unit.prefixes
+                    .as_ref()
+                    .map(|prefixes| format!("prefixes: {}", prefixes)),
+                dimensions
+                    .contains(&(unit.dimension.clone(), true))
+                    .then(|| format!("kind: {}", to_pascal_case(&unit.dimension))),
+                unit.display
+                    .as_ref()
+                    .map(|display| format!("display: {:?}", display)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+
+            if !options.is_empty() {
+                write!(&mut code, " [{}]", options.join(", "))?;
+            }
+
+            writeln!(&mut code, ",")?;
+        }
+
+        if !powered.is_empty() {
+            writeln!(&mut code, ";")?;
+        }
+
+        for unit in powered {
+            // invalid powers have been reported by `validate_units`
+            let Some(base) = units.iter().find(|base| base.symbol == unit.base) else {
+                continue;
+            };
+
+            let (per, suffix) = match unit.exponent {
+                -1 => ("per_", String::new()),
+                exp if exp < 0 => ("per_", exp.unsigned_abs().to_string()),
+                exp => ("", exp.to_string()),
+            };
+
+            write!(
                 &mut code,
-                "    {} ({}): {},",
-                unit.name, unit.symbol, dimension_type
+                "    {} ({}[{}]{}): {} ^ {}{}",
+                unit.name,
+                per,
+                unit.base,
+                if suffix.is_empty() {
+                    suffix
+                } else {
+                    format!(" {}", suffix)
+                },
+                to_pascal_case(&base.dimension),
+                if unit.exponent < 0 { 'N' } else { 'P' },
+                unit.exponent.unsigned_abs()
             )?;
+
+            // the prefixed powers exist only for the prefixes of the base unit
+            if let Some(prefixes) = &base.prefixes {
+                write!(&mut code, " [prefixes: {}]", prefixes)?;
+            }
+
+            writeln!(&mut code, ",")?;
         }
 
         writeln!(&mut code, "}}")?;
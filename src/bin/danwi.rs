@@ -0,0 +1,181 @@
+//! Command-line calculator for expressions with units.
+//!
+//! ```text
+//! danwi '680 kg/s * 350 s * g0' --to MN
+//! danwi 10.5mV to uV
+//! danwi                                   # interactive mode
+//! ```
+
+use danwi::{
+    DynQuantity,
+    dynamic::{ConversionError, EvalError, ParseError, convert_quantity, eval},
+};
+use std::{
+    env,
+    io::{self, BufRead, IsTerminal, Write},
+    ops::Range,
+    process::ExitCode,
+};
+
+const USAGE: &str = "\
+usage: danwi [EXPRESSION] [(--to | to) UNIT]
+
+Evaluates an expression with units, e.g. `5 mA * 2 kOhm`, and prints the
+result in the coherent SI unit or in UNIT. Without an expression, reads one
+expression per line from standard input.
+
+options:
+  -t, --to UNIT    convert the result to UNIT
+  -h, --help       print this help
+  -V, --version    print the version";
+
+fn main() -> ExitCode {
+    let mut words = Vec::new();
+    let mut target = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "-V" | "--version" => {
+                println!("danwi {}", env!("CARGO_PKG_VERSION"));
+                return ExitCode::SUCCESS;
+            }
+            "-t" | "--to" => match args.next() {
+                Some(unit) => target = Some(unit),
+                None => {
+                    eprintln!("error: `{}` requires a unit\n\n{}", arg, USAGE);
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => words.push(arg),
+        }
+    }
+
+    if words.is_empty() {
+        return repl();
+    }
+
+    let line = words.join(" ");
+    let (expr, to) = split_target(&line);
+
+    match run(expr, to.or(target.as_deref())) {
+        Ok(result) => {
+            println!("{}", result);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Evaluates one expression per line until the end of the input.
+fn repl() -> ExitCode {
+    let interactive = io::stdin().is_terminal();
+    let mut lines = io::stdin().lock().lines();
+    let mut status = ExitCode::SUCCESS;
+
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+
+        let Some(Ok(line)) = lines.next() else {
+            return status;
+        };
+
+        let line = line.trim();
+
+        match line {
+            "" => continue,
+            "exit" | "quit" => return status,
+            _ => {}
+        }
+
+        let (expr, to) = split_target(line);
+
+        match run(expr, to) {
+            Ok(result) => println!("{}", result),
+            Err(err) => {
+                eprintln!("{}", err);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+}
+
+/// Splits `10.5mV to uV` into the expression and the target unit.
+fn split_target(line: &str) -> (&str, Option<&str>) {
+    match line.rsplit_once(" to ") {
+        Some((expr, unit)) => (expr.trim(), Some(unit.trim())),
+        None => (line, None),
+    }
+}
+
+/// Evaluates `expr` and formats the result, converted to `unit` if given.
+fn run(expr: &str, unit: Option<&str>) -> Result<String, String> {
+    let result = eval(expr).map_err(|err| eval_error(expr, &err))?;
+
+    let Some(unit) = unit else {
+        let result = DynQuantity::new(round(result.value()), result.exponents())
+            .with_extra_exponents(result.extra_exponents())
+            .with_kind(result.kind());
+        return Ok(result.to_string());
+    };
+
+    let value = convert_quantity(result, unit).map_err(|err| match err {
+        ConversionError::Dimension(mismatch) => format!(
+            "error: cannot convert {} to {}",
+            mismatch.found_dimension(),
+            mismatch.expected_dimension()
+        ),
+        ConversionError::Unit(err) | ConversionError::Value(err) => parse_error(unit, &err),
+    })?;
+
+    Ok(format!("{} {}", round(value), unit))
+}
+
+/// Rounds to 12 significant digits to hide floating-point noise.
+fn round(value: f64) -> f64 {
+    format!("{:.11e}", value).parse().unwrap_or(value)
+}
+
+fn eval_error(src: &str, err: &EvalError) -> String {
+    match err {
+        EvalError::Parse(err) => parse_error(src, err),
+        EvalError::Dimension { mismatch, lhs, rhs } => format!(
+            "error: cannot add or subtract {} and {}\n{}",
+            mismatch.expected_dimension(),
+            mismatch.found_dimension(),
+            underline(src, &[lhs.clone(), rhs.clone()])
+        ),
+    }
+}
+
+fn parse_error(src: &str, err: &ParseError) -> String {
+    format!("error: {}\n{}", err.kind(), underline(src, &[err.span()]))
+}
+
+/// Renders the source with the given byte ranges underlined.
+fn underline(src: &str, spans: &[Range<usize>]) -> String {
+    let mut marks = String::new();
+
+    // one mark per character, so that multi-byte characters stay aligned
+    for (i, _) in src.char_indices() {
+        let marked = spans.iter().any(|span| span.contains(&i));
+        marks.push(if marked { '^' } else { ' ' });
+    }
+
+    // errors at the end of the input point just past it
+    if spans.iter().any(|span| span.start >= src.len()) {
+        marks.push('^');
+    }
+
+    format!("  {}\n  {}", src, marks.trim_end())
+}
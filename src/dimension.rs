@@ -1,7 +1,23 @@
 //! Type-level dimensions with compile-time arithmetics.
 
-use core::ops::{Add, Neg, Sub};
-use typenum::{Diff, Integer, Negate, Prod, Sum};
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+use typenum::{ATerm, Diff, Integer, N1, Negate, Prod, Sum, Z0};
+
+mod extra;
+mod name;
+
+pub use extra::{
+    AddExtra, EXTRA_DIMENSIONS, ExtraExponents, ExtraProd, ExtraSum, PrependExponent, SameExtra,
+    ScaleExtra,
+};
+pub use name::{AddableTo, DimensionName, SubtractableFrom, Unnamed};
+#[doc(hidden)]
+pub use name::{
+    Branch, CmpDimensions, CmpExtra, Exponents, FirstUnequal, NameLookup, NameNode, Never,
+};
 
 /// Type-level dimension representation.
 ///
@@ -13,10 +29,51 @@ use typenum::{Diff, Integer, Negate, Prod, Sum};
 /// - K: Thermodynamic Temperature (kelvin, K)
 /// - N: Amount of Substance (mole, mol)
 /// - J: Luminous Intensity (candela, cd)
-pub type Dimension<T, L, M, I, K, N, J> = (T, L, M, I, K, N, J);
+///
+/// The optional X holds the exponents of up to [`EXTRA_DIMENSIONS`] additional
+/// base dimensions as a `typenum` array (e.g. `tarr![P1]`), and must not end in
+/// `Z0`. The first one (X1) is information, in bits; the others are free for
+/// downstream crates to use for pixels, currency and the like, with a meaning
+/// that is up to the crate that declares them; see [`ExtraDimension`].
+pub type Dimension<T, L, M, I, K, N, J, X = ATerm> = (T, L, M, I, K, N, J, X);
+
+/// An additional base dimension, given by its exponents beyond the seven SI
+/// base dimensions.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{
+///     Quantity, Unit,
+///     dimension::{Dimensionless, ExtraDimension, Length},
+///     f64::{QuantityExt, constants::b},
+/// };
+/// use typenum::{P1, Z0, tarr};
+///
+/// // X1 is danwi's information
+/// type Pixels = ExtraDimension<tarr![Z0, P1]>;
+///
+/// #[allow(non_upper_case_globals)]
+/// const px: Unit<Pixels> = Unit::base();
+///
+/// let bandwidth = 4.0_f64 * b / 1.0.ms();
+/// assert_eq!(bandwidth * 2.0.s(), 8000.0 * b);
+/// assert_eq!(bandwidth.dimension().to_string(), "DataRate (T⁻¹·X1)");
+/// assert_eq!((bandwidth * px).dimension().to_string(), "T⁻¹·X1·X2");
+///
+/// let pitch: Quantity<f64, danwi::dim!(Pixels / Length)> = 1920.0 * px / 24.0.cm();
+/// assert_eq!(pitch * 1.0.mm(), 8.0 * px);
+///
+/// let ratio: Quantity<f64, Dimensionless> = (3.0 * px) / (2.0 * px);
+/// assert_eq!(ratio.value(), 1.5);
+/// ```
+pub type ExtraDimension<X> = Dimension<Z0, Z0, Z0, Z0, Z0, Z0, Z0, X>;
 
 /// Trait to extract type parameters from a Dimension.
-pub trait Dimensions {
+///
+/// Every dimension has a [name](DimensionName), so that generic code can add
+/// and subtract quantities of a dimension `D: Dimensions`.
+pub trait Dimensions: DimensionName {
     type T: Integer;
     type L: Integer;
     type M: Integer;
@@ -24,10 +81,51 @@ pub trait Dimensions {
     type K: Integer;
     type N: Integer;
     type J: Integer;
+    type X: ExtraExponents;
+
+    /// Exponents of the SI base dimensions, in the order T, L, M, I, K, N, J.
+    const EXPONENTS: [i8; 7];
+
+    /// Name of the dimension if it is defined in `dimensions.txt`.
+    ///
+    /// Kinds of quantity sharing their exponents (see
+    /// [`UnitInfo::kind`](crate::unit::registry::UnitInfo::kind)) are the same
+    /// type, named after the dimension without a kind, or after the first kind
+    /// if all of them are kinds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use danwi::dimension::{Dimensions, Force};
+    ///
+    /// assert_eq!(Force::NAME, Some("Force"));
+    /// assert_eq!(<danwi::dim!(L ^ 5)>::NAME, None);
+    ///
+    /// # #[cfg(all(feature = "kinematic", feature = "radiological"))] {
+    /// use danwi::{dimension::{AbsorbedDose, Activity}, f64::QuantityExt};
+    ///
+    /// assert_eq!(AbsorbedDose::NAME, Some("SpecificEnergy"));
+    /// assert_eq!(Activity::NAME, Some("Frequency"));
+    /// assert_eq!(
+    ///     (1.0.Gy() + 1.0.Sv()).dimension().to_string(),
+    ///     "SpecificEnergy (L²·T⁻²)"
+    /// );
+    /// # }
+    /// ```
+    const NAME: Option<&'static str>;
 }
 
-impl<T: Integer, L: Integer, M: Integer, I: Integer, K: Integer, N: Integer, J: Integer> Dimensions
-    for Dimension<T, L, M, I, K, N, J>
+impl<T, L, M, I, K, N, J, X> Dimensions for Dimension<T, L, M, I, K, N, J, X>
+where
+    T: Integer,
+    L: Integer,
+    M: Integer,
+    I: Integer,
+    K: Integer,
+    N: Integer,
+    J: Integer,
+    X: ExtraExponents,
+    Self: DimensionName,
 {
     type T = T;
     type L = L;
@@ -36,6 +134,244 @@ impl<T: Integer, L: Integer, M: Integer, I: Integer, K: Integer, N: Integer, J:
     type K = K;
     type N = N;
     type J = J;
+    type X = X;
+
+    const EXPONENTS: [i8; 7] = [T::I8, L::I8, M::I8, I::I8, K::I8, N::I8, J::I8];
+    const NAME: Option<&'static str> = name_of(&Self::EXPONENTS, &X::EXPONENTS);
+}
+
+/// Runtime description of a dimension.
+///
+/// # Examples
+///
+/// ```
+/// # use danwi::prelude::*;
+/// let v = 3.3.V();
+/// let dim = v.dimension();
+/// assert_eq!(dim.exponents(), [-3, 2, 1, -1, 0, 0, 0]);
+/// assert_eq!(dim.name(), Some("Voltage"));
+/// assert_eq!(dim.to_string(), "Voltage (M·L²·T⁻³·I⁻¹)");
+///
+/// let dim = (v / 1.0.m()).dimension();
+/// assert_eq!(dim.name(), Some("ElectricFieldStrength"));
+///
+/// let dim = (v * 1.0.m()).dimension();
+/// assert_eq!(dim.name(), None);
+/// assert_eq!(dim.to_string(), "M·L³·T⁻³·I⁻¹");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DimensionInfo {
+    exponents: [i8; 7],
+    extra: [i8; EXTRA_DIMENSIONS],
+    name: Option<&'static str>,
+}
+
+impl DimensionInfo {
+    /// Returns the description of the dimension `D`.
+    pub const fn of<D: Dimensions>() -> Self {
+        Self {
+            exponents: D::EXPONENTS,
+            extra: <D::X as ExtraExponents>::EXPONENTS,
+            name: D::NAME,
+        }
+    }
+
+    /// Returns the description of the dimension with the given exponents,
+    /// looking up its name in `dimensions.txt`.
+    pub const fn from_exponents(exponents: [i8; 7]) -> Self {
+        Self::from_exponents_with_extra(exponents, [0; EXTRA_DIMENSIONS])
+    }
+
+    /// Returns the description of the dimension with the given exponents of
+    /// the SI and the additional base dimensions (e.g. `[1, 0, ...]` for
+    /// information), looking up its name in `dimensions.txt`.
+    pub const fn from_exponents_with_extra(
+        exponents: [i8; 7],
+        extra: [i8; EXTRA_DIMENSIONS],
+    ) -> Self {
+        Self {
+            exponents,
+            extra,
+            name: name_of(&exponents, &extra),
+        }
+    }
+
+    /// Returns the exponents of the SI base dimensions (T, L, M, I, K, N, J).
+    #[inline]
+    pub const fn exponents(&self) -> [i8; 7] {
+        self.exponents
+    }
+
+    /// Returns the exponents of the additional base dimensions (see
+    /// [`Dimension`]).
+    #[inline]
+    pub const fn extra_exponents(&self) -> [i8; EXTRA_DIMENSIONS] {
+        self.extra
+    }
+
+    /// Returns the name of the dimension, if it has one.
+    #[inline]
+    pub const fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Returns `true` if all exponents are zero.
+    #[inline]
+    pub const fn is_dimensionless(&self) -> bool {
+        is_zero(&self.exponents) && is_zero(&self.extra)
+    }
+}
+
+impl fmt::Display for DimensionInfo {
+    /// Formats the dimension as its name followed by its base dimensions, e.g.
+    /// `Force (M·L·T⁻²)`, or only the base dimensions if it has no name.
+    /// Additional base dimensions are written as `X1`, `X2`, etc.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.name {
+            write!(f, "{} (", name)?;
+        }
+
+        self.write_base_dimensions(f)?;
+
+        if self.name.is_some() {
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DimensionInfo {
+    /// Writes the dimension as a product of base dimensions (e.g. `M·L·T⁻²`),
+    /// or `1` if it is dimensionless.
+    pub(crate) fn write_base_dimensions(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.is_dimensionless() {
+            write!(f, "1")?;
+        }
+
+        write_product(f, &self.exponents, &["T", "L", "M", "I", "K", "N", "J"])?;
+
+        let mut first = is_zero(&self.exponents);
+
+        for (i, &exp) in self.extra.iter().enumerate() {
+            if exp == 0 {
+                continue;
+            }
+
+            if !first {
+                f.write_char('·')?;
+            }
+
+            write!(f, "X{}", i + 1)?;
+            write_superscript(f, exp)?;
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a product of powers of base symbols (e.g. `kg·m²·s⁻³`), in the
+/// conventional order M, L, T, I, K, N, J.
+pub(crate) fn write_product(
+    f: &mut impl fmt::Write,
+    exponents: &[i8; 7],
+    symbols: &[&str; 7],
+) -> fmt::Result {
+    const ORDER: [usize; 7] = [2, 1, 0, 3, 4, 5, 6];
+
+    let mut first = true;
+
+    for i in ORDER {
+        let exp = exponents[i];
+
+        if exp == 0 {
+            continue;
+        }
+
+        if !first {
+            f.write_char('·')?;
+        }
+
+        f.write_str(symbols[i])?;
+        write_superscript(f, exp)?;
+        first = false;
+    }
+
+    Ok(())
+}
+
+/// Writes an exponent as superscript characters, omitting an exponent of one.
+pub(crate) fn write_superscript(f: &mut impl fmt::Write, exp: i8) -> fmt::Result {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+    if exp == 1 {
+        return Ok(());
+    }
+
+    if exp < 0 {
+        f.write_char('⁻')?;
+    }
+
+    let exp = exp.unsigned_abs();
+
+    if exp >= 100 {
+        f.write_char(DIGITS[(exp / 100) as usize])?;
+    }
+
+    if exp >= 10 {
+        f.write_char(DIGITS[(exp / 10 % 10) as usize])?;
+    }
+
+    f.write_char(DIGITS[(exp % 10) as usize])
+}
+
+/// Returns `true` if all exponents are zero.
+const fn is_zero(exponents: &[i8]) -> bool {
+    let mut i = 0;
+    while i < exponents.len() {
+        if exponents[i] != 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Looks up the name of the dimension with the given exponents.
+const fn name_of(exponents: &[i8; 7], extra: &[i8; EXTRA_DIMENSIONS]) -> Option<&'static str> {
+    let mut i = 0;
+
+    'outer: while i < NAMED_DIMENSIONS.len() {
+        let (name, named, named_extra) = NAMED_DIMENSIONS[i];
+        i += 1;
+
+        let mut j = 0;
+        while j < named.len() {
+            if named[j] != exponents[j] {
+                continue 'outer;
+            }
+            j += 1;
+        }
+
+        // the named dimensions list the leading extra exponents
+        let mut j = 0;
+        while j < EXTRA_DIMENSIONS {
+            let named = if j < named_extra.len() {
+                named_extra[j]
+            } else {
+                0
+            };
+            if named != extra[j] {
+                continue 'outer;
+            }
+            j += 1;
+        }
+
+        return Some(name);
+    }
+
+    None
 }
 
 /// Add two dimensions.
@@ -47,6 +383,7 @@ pub type DimensionAdd<D1, D2> = Dimension<
     Sum<<D1 as Dimensions>::K, <D2 as Dimensions>::K>,
     Sum<<D1 as Dimensions>::N, <D2 as Dimensions>::N>,
     Sum<<D1 as Dimensions>::J, <D2 as Dimensions>::J>,
+    ExtraSum<<D1 as Dimensions>::X, <D2 as Dimensions>::X>,
 >;
 
 /// Subtract two dimensions.
@@ -58,6 +395,7 @@ pub type DimensionSub<D1, D2> = Dimension<
     Diff<<D1 as Dimensions>::K, <D2 as Dimensions>::K>,
     Diff<<D1 as Dimensions>::N, <D2 as Dimensions>::N>,
     Diff<<D1 as Dimensions>::J, <D2 as Dimensions>::J>,
+    ExtraSum<<D1 as Dimensions>::X, ExtraProd<<D2 as Dimensions>::X, N1>>,
 >;
 
 /// Multiply two dimensions (add exponents).
@@ -75,6 +413,7 @@ pub type DimensionPow<D, E> = Dimension<
     Prod<<D as Dimensions>::K, E>,
     Prod<<D as Dimensions>::N, E>,
     Prod<<D as Dimensions>::J, E>,
+    ExtraProd<<D as Dimensions>::X, E>,
 >;
 
 /// Reciprocal of a dimension (negate all exponents).
@@ -86,15 +425,62 @@ pub type DimensionRecip<D> = Dimension<
     Negate<<D as Dimensions>::K>,
     Negate<<D as Dimensions>::N>,
     Negate<<D as Dimensions>::J>,
+    ExtraProd<<D as Dimensions>::X, N1>,
 >;
 
+/// Helper trait for adding quantities, which requires both dimensions to be
+/// the same.
+///
+/// Dimensions are compared by their [names](DimensionName), so that a
+/// mismatch is reported with them, e.g. as ``cannot add `ElectricCurrent` to
+/// `Voltage` ``.
+#[diagnostic::on_unimplemented(
+    message = "cannot add a quantity of a different dimension",
+    label = "this quantity has dimension `{Self}`",
+    note = "the right-hand side has dimension `{Rhs}`",
+    note = "quantities can only be added to quantities of the same dimension"
+)]
+pub trait CanAdd<Rhs: Dimensions>: Dimensions {}
+
+impl<Lhs, Rhs> CanAdd<Rhs> for Lhs
+where
+    Lhs: Dimensions,
+    Rhs: Dimensions,
+    <Lhs as DimensionName>::Name: AddableTo<<Rhs as DimensionName>::Name>,
+{
+}
+
+/// Helper trait for subtracting quantities, which requires both dimensions to
+/// be the same.
+#[diagnostic::on_unimplemented(
+    message = "cannot subtract a quantity of a different dimension",
+    label = "this quantity has dimension `{Self}`",
+    note = "the right-hand side has dimension `{Rhs}`",
+    note = "quantities can only be subtracted from quantities of the same dimension"
+)]
+pub trait CanSubtract<Rhs: Dimensions>: Dimensions {}
+
+impl<Lhs, Rhs> CanSubtract<Rhs> for Lhs
+where
+    Lhs: Dimensions,
+    Rhs: Dimensions,
+    <Lhs as DimensionName>::Name: SubtractableFrom<<Rhs as DimensionName>::Name>,
+{
+}
+
 /// Helper trait for dimension multiplication operations.
 /// Encapsulates all the trait bounds needed for multiplying two dimensions.
+#[diagnostic::on_unimplemented(
+    message = "cannot multiply a quantity of dimension `{Self}` by one of dimension `{Rhs}`",
+    label = "`{Self}` cannot be multiplied by `{Rhs}`",
+    note = "both operands must be quantities with dimensions built from `typenum` integers"
+)]
 pub trait CanMultiplyWith<Rhs: Dimensions>: Dimensions {
     type Output: Dimensions;
 }
 
 // Blanket implementation for all valid dimension combinations
+#[diagnostic::do_not_recommend]
 impl<Lhs, Rhs> CanMultiplyWith<Rhs> for Lhs
 where
     Lhs: Dimensions,
@@ -113,17 +499,25 @@ where
     Sum<<Lhs as Dimensions>::K, <Rhs as Dimensions>::K>: Integer,
     Sum<<Lhs as Dimensions>::N, <Rhs as Dimensions>::N>: Integer,
     Sum<<Lhs as Dimensions>::J, <Rhs as Dimensions>::J>: Integer,
+    <Lhs as Dimensions>::X: AddExtra<<Rhs as Dimensions>::X>,
+    DimensionMul<Lhs, Rhs>: DimensionName,
 {
     type Output = DimensionMul<Lhs, Rhs>;
 }
 
 /// Helper trait for dimension division operations.
 /// Encapsulates all the trait bounds needed for dividing two dimensions.
+#[diagnostic::on_unimplemented(
+    message = "cannot divide a quantity of dimension `{Self}` by one of dimension `{Rhs}`",
+    label = "`{Self}` cannot be divided by `{Rhs}`",
+    note = "both operands must be quantities with dimensions built from `typenum` integers"
+)]
 pub trait CanDivideBy<Rhs: Dimensions>: Dimensions {
     type Output: Dimensions;
 }
 
 // Blanket implementation for all valid dimension combinations
+#[diagnostic::do_not_recommend]
 impl<Lhs, Rhs> CanDivideBy<Rhs> for Lhs
 where
     Lhs: Dimensions,
@@ -142,15 +536,24 @@ where
     Diff<<Lhs as Dimensions>::K, <Rhs as Dimensions>::K>: Integer,
     Diff<<Lhs as Dimensions>::N, <Rhs as Dimensions>::N>: Integer,
     Diff<<Lhs as Dimensions>::J, <Rhs as Dimensions>::J>: Integer,
+    <Rhs as Dimensions>::X: ScaleExtra<N1>,
+    <Lhs as Dimensions>::X: AddExtra<ExtraProd<<Rhs as Dimensions>::X, N1>>,
+    DimensionDiv<Lhs, Rhs>: DimensionName,
 {
     type Output = DimensionDiv<Lhs, Rhs>;
 }
 
 /// Helper trait for dimensions that can be reciprocated (1/D).
+#[diagnostic::on_unimplemented(
+    message = "cannot take the reciprocal of a quantity of dimension `{Self}`",
+    label = "`{Self}` cannot be reciprocated",
+    note = "the quantity must have a dimension built from `typenum` integers"
+)]
 pub trait CanReciprocate: Dimensions {
     type Output: Dimensions;
 }
 
+#[diagnostic::do_not_recommend]
 impl<D> CanReciprocate for D
 where
     D: Dimensions,
@@ -168,8 +571,45 @@ where
     Negate<<D as Dimensions>::K>: Integer,
     Negate<<D as Dimensions>::N>: Integer,
     Negate<<D as Dimensions>::J>: Integer,
+    <D as Dimensions>::X: ScaleExtra<N1>,
+    DimensionRecip<D>: DimensionName,
 {
     type Output = DimensionRecip<D>;
 }
 
+/// Helper trait for dimensions that can be raised to an integer power (D^E).
+#[diagnostic::on_unimplemented(
+    message = "cannot raise a quantity of dimension `{Self}` to the power `{E}`",
+    label = "`{Self}` cannot be raised to `{E}`",
+    note = "the exponent must be a `typenum` integer such as `P2` or `N1`"
+)]
+pub trait CanRaiseTo<E: Integer>: Dimensions {
+    type Output: Dimensions;
+}
+
+#[diagnostic::do_not_recommend]
+impl<D, E> CanRaiseTo<E> for D
+where
+    D: Dimensions,
+    E: Integer,
+    <D as Dimensions>::T: Mul<E>,
+    <D as Dimensions>::L: Mul<E>,
+    <D as Dimensions>::M: Mul<E>,
+    <D as Dimensions>::I: Mul<E>,
+    <D as Dimensions>::K: Mul<E>,
+    <D as Dimensions>::N: Mul<E>,
+    <D as Dimensions>::J: Mul<E>,
+    Prod<<D as Dimensions>::T, E>: Integer,
+    Prod<<D as Dimensions>::L, E>: Integer,
+    Prod<<D as Dimensions>::M, E>: Integer,
+    Prod<<D as Dimensions>::I, E>: Integer,
+    Prod<<D as Dimensions>::K, E>: Integer,
+    Prod<<D as Dimensions>::N, E>: Integer,
+    Prod<<D as Dimensions>::J, E>: Integer,
+    <D as Dimensions>::X: ScaleExtra<E>,
+    DimensionPow<D, E>: DimensionName,
+{
+    type Output = DimensionPow<D, E>;
+}
+
 include!(concat!(env!("OUT_DIR"), "/dimensions_generated.rs"));
@@ -0,0 +1,136 @@
+//! Type-level arithmetics on the exponents of additional base dimensions.
+//!
+//! The exponents are stored as a `typenum` array such as `tarr![P1, Z0, N2]`,
+//! where a missing exponent is zero. Arrays must not end in `Z0`, and results
+//! are normalized by dropping trailing zeros, so that e.g. `bit/bit` has the
+//! exponents `ATerm` of a plain SI dimension.
+
+use core::ops::{Add, Mul};
+use typenum::{ATerm, Integer, IsEqual, NInt, NonZero, PInt, Prod, Sum, TArr, True, Unsigned, Z0};
+
+/// Maximum number of additional base dimensions.
+pub const EXTRA_DIMENSIONS: usize = 8;
+
+/// Exponents of additional base dimensions.
+pub trait ExtraExponents {
+    /// The exponents, padded with zeros.
+    const EXPONENTS: [i8; EXTRA_DIMENSIONS];
+}
+
+impl ExtraExponents for ATerm {
+    const EXPONENTS: [i8; EXTRA_DIMENSIONS] = [0; EXTRA_DIMENSIONS];
+}
+
+impl<V: Integer, A: ExtraExponents> ExtraExponents for TArr<V, A> {
+    const EXPONENTS: [i8; EXTRA_DIMENSIONS] = prepend(V::I8, A::EXPONENTS);
+}
+
+const fn prepend(head: i8, tail: [i8; EXTRA_DIMENSIONS]) -> [i8; EXTRA_DIMENSIONS] {
+    assert!(
+        tail[EXTRA_DIMENSIONS - 1] == 0,
+        "too many additional base dimensions"
+    );
+
+    let mut exponents = [head; EXTRA_DIMENSIONS];
+    let mut i = 1;
+    while i < EXTRA_DIMENSIONS {
+        exponents[i] = tail[i - 1];
+        i += 1;
+    }
+    exponents
+}
+
+/// Prepends an exponent to normalized exponents, dropping it if it is a
+/// trailing zero.
+pub trait PrependExponent<A> {
+    type Output: ExtraExponents;
+}
+
+impl PrependExponent<ATerm> for Z0 {
+    type Output = ATerm;
+}
+
+impl<V: Integer, A: ExtraExponents> PrependExponent<TArr<V, A>> for Z0 {
+    type Output = TArr<Z0, TArr<V, A>>;
+}
+
+impl<U: Unsigned + NonZero, A: ExtraExponents> PrependExponent<A> for PInt<U> {
+    type Output = TArr<PInt<U>, A>;
+}
+
+impl<U: Unsigned + NonZero, A: ExtraExponents> PrependExponent<A> for NInt<U> {
+    type Output = TArr<NInt<U>, A>;
+}
+
+/// Adds exponents element-wise (multiplication of dimensions).
+pub trait AddExtra<Rhs> {
+    type Output: ExtraExponents;
+}
+
+/// Sum of the exponents `A` and `B`.
+pub type ExtraSum<A, B> = <A as AddExtra<B>>::Output;
+
+impl AddExtra<ATerm> for ATerm {
+    type Output = ATerm;
+}
+
+// the results of all operations are normalized, so adding nothing keeps them
+impl<V: Integer, A: ExtraExponents> AddExtra<TArr<V, A>> for ATerm {
+    type Output = TArr<V, A>;
+}
+
+impl<V: Integer, A: ExtraExponents> AddExtra<ATerm> for TArr<V, A> {
+    type Output = TArr<V, A>;
+}
+
+impl<V1, A1, V2, A2> AddExtra<TArr<V2, A2>> for TArr<V1, A1>
+where
+    V1: Add<V2>,
+    A1: AddExtra<A2>,
+    Sum<V1, V2>: PrependExponent<ExtraSum<A1, A2>>,
+{
+    type Output = <Sum<V1, V2> as PrependExponent<ExtraSum<A1, A2>>>::Output;
+}
+
+/// Multiplies exponents by the integer `E` (powers of dimensions).
+pub trait ScaleExtra<E> {
+    type Output: ExtraExponents;
+}
+
+/// Exponents `A` multiplied by `E`.
+pub type ExtraProd<A, E> = <A as ScaleExtra<E>>::Output;
+
+impl<E> ScaleExtra<E> for ATerm {
+    type Output = ATerm;
+}
+
+impl<V, A, E> ScaleExtra<E> for TArr<V, A>
+where
+    V: Mul<E>,
+    A: ScaleExtra<E>,
+    Prod<V, E>: PrependExponent<ExtraProd<A, E>>,
+{
+    type Output = <Prod<V, E> as PrependExponent<ExtraProd<A, E>>>::Output;
+}
+
+/// Implemented if both exponents are equal, treating missing exponents as
+/// zero.
+pub trait SameExtra<Rhs> {}
+
+impl SameExtra<ATerm> for ATerm {}
+
+impl<V, A> SameExtra<TArr<V, A>> for ATerm where TArr<V, A>: SameExtra<ATerm> {}
+
+impl<V, A> SameExtra<ATerm> for TArr<V, A>
+where
+    V: IsEqual<Z0, Output = True>,
+    A: SameExtra<ATerm>,
+{
+}
+
+impl<V1, A1, V2, A2> SameExtra<TArr<V2, A2>> for TArr<V1, A1>
+where
+    V1: IsEqual<V2, Output = True>,
+    A1: SameExtra<A2>,
+{
+}
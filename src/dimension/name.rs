@@ -0,0 +1,257 @@
+//! Type-level lookup of the names of dimensions, for compile errors.
+//!
+//! The dimensions of `dimensions.txt` (except kinds) are generated as a binary
+//! search tree of [`NameNode`]s ordered by their exponents, which maps a
+//! dimension such as `(N3, P2, P1, N1, Z0, Z0, Z0, ATerm)` to a marker type
+//! such as `names::Voltage` in a few steps.
+
+use super::NameTree;
+use core::marker::PhantomData;
+use typenum::{ATerm, Cmp, Compare, Equal, Greater, Less, TArr, Z0};
+
+/// Name of a dimension in compile errors: the marker type of its name in
+/// `dimensions.txt` (e.g. `names::Voltage`), or [`Unnamed`] if it has none.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::dimension::{DimensionName, Length, Unnamed, names};
+///
+/// fn named<D: DimensionName<Name = N>, N>() {}
+///
+/// named::<Length, names::Length>();
+/// named::<danwi::dim!(L * L), names::Area>();
+/// named::<danwi::dim!(L ^ 5), Unnamed<danwi::dim!(L ^ 5)>>();
+/// ```
+pub trait DimensionName {
+    type Name;
+}
+
+// every `Dimensions` has a name, so that generic code can add quantities
+impl<D> DimensionName for D
+where
+    NameTree: NameLookup<D>,
+{
+    type Name = <NameTree as NameLookup<D>>::Output;
+}
+
+/// Name of a dimension that has none in `dimensions.txt`.
+pub struct Unnamed<D>(PhantomData<D>);
+
+/// Node of the tree of named dimensions, with the dimension `K`, its name `N`
+/// and the subtrees of the dimensions ordered before (`L`) and after (`R`) it.
+#[doc(hidden)]
+pub struct NameNode<K, N, L, R>(PhantomData<(K, N, L, R)>);
+
+/// Looks up the name of the dimension `D` in a tree of named dimensions.
+#[doc(hidden)]
+pub trait NameLookup<D> {
+    type Output;
+}
+
+impl<D> NameLookup<D> for () {
+    type Output = Unnamed<D>;
+}
+
+impl<D, K, N, L, R> NameLookup<D> for NameNode<K, N, L, R>
+where
+    D: CmpDimensions<K>,
+    <D as CmpDimensions<K>>::Output: Branch<D, N, L, R>,
+{
+    type Output = <<D as CmpDimensions<K>>::Output as Branch<D, N, L, R>>::Output;
+}
+
+/// Continues the lookup of `D` by the ordering of `D` and the dimension of a
+/// node.
+#[doc(hidden)]
+pub trait Branch<D, N, L, R> {
+    type Output;
+}
+
+impl<D, N, L, R> Branch<D, N, L, R> for Equal {
+    type Output = N;
+}
+
+impl<D, N, L: NameLookup<D>, R> Branch<D, N, L, R> for Less {
+    type Output = L::Output;
+}
+
+impl<D, N, L, R: NameLookup<D>> Branch<D, N, L, R> for Greater {
+    type Output = R::Output;
+}
+
+/// Orders dimensions lexicographically by their exponents (T, L, M, I, K, N,
+/// J and the additional ones), as `Less`, `Equal` or `Greater`.
+#[doc(hidden)]
+pub trait CmpDimensions<Rhs> {
+    type Output;
+}
+
+// on the tuples rather than on `Dimensions`, which requires a name
+impl<T1, L1, M1, I1, K1, N1, J1, X1, T2, L2, M2, I2, K2, N2, J2, X2>
+    CmpDimensions<(T2, L2, M2, I2, K2, N2, J2, X2)> for (T1, L1, M1, I1, K1, N1, J1, X1)
+where
+    T1: Cmp<T2>,
+    L1: Cmp<L2>,
+    M1: Cmp<M2>,
+    I1: Cmp<I2>,
+    K1: Cmp<K2>,
+    N1: Cmp<N2>,
+    J1: Cmp<J2>,
+    X1: CmpExtra<X2>,
+    Orderings<(T1, L1, M1, I1, K1, N1, J1, X1), (T2, L2, M2, I2, K2, N2, J2, X2)>: FirstUnequal,
+{
+    type Output = <Orderings<
+        (T1, L1, M1, I1, K1, N1, J1, X1),
+        (T2, L2, M2, I2, K2, N2, J2, X2),
+    > as FirstUnequal>::Output;
+}
+
+/// Orderings of the exponents of two dimensions, one by one.
+type Orderings<Lhs, Rhs> = TArr<
+    Compare<<Lhs as Exponents>::T, <Rhs as Exponents>::T>,
+    TArr<
+        Compare<<Lhs as Exponents>::L, <Rhs as Exponents>::L>,
+        TArr<
+            Compare<<Lhs as Exponents>::M, <Rhs as Exponents>::M>,
+            TArr<
+                Compare<<Lhs as Exponents>::I, <Rhs as Exponents>::I>,
+                TArr<
+                    Compare<<Lhs as Exponents>::K, <Rhs as Exponents>::K>,
+                    TArr<
+                        Compare<<Lhs as Exponents>::N, <Rhs as Exponents>::N>,
+                        TArr<
+                            Compare<<Lhs as Exponents>::J, <Rhs as Exponents>::J>,
+                            TArr<
+                                <<Lhs as Exponents>::X as CmpExtra<<Rhs as Exponents>::X>>::Output,
+                                ATerm,
+                            >,
+                        >,
+                    >,
+                >,
+            >,
+        >,
+    >,
+>;
+
+/// The exponents of a dimension tuple, without the bounds of `Dimensions`.
+#[doc(hidden)]
+pub trait Exponents {
+    type T;
+    type L;
+    type M;
+    type I;
+    type K;
+    type N;
+    type J;
+    type X;
+}
+
+impl<T, L, M, I, K, N, J, X> Exponents for (T, L, M, I, K, N, J, X) {
+    type T = T;
+    type L = L;
+    type M = M;
+    type I = I;
+    type K = K;
+    type N = N;
+    type J = J;
+    type X = X;
+}
+
+/// Orders exponents of additional base dimensions lexicographically, treating
+/// missing exponents as zero.
+#[doc(hidden)]
+pub trait CmpExtra<Rhs> {
+    type Output;
+}
+
+impl CmpExtra<ATerm> for ATerm {
+    type Output = Equal;
+}
+
+impl<V, A> CmpExtra<ATerm> for TArr<V, A>
+where
+    V: Cmp<Z0>,
+    A: CmpExtra<ATerm>,
+    TArr<Compare<V, Z0>, TArr<A::Output, ATerm>>: FirstUnequal,
+{
+    type Output = <TArr<Compare<V, Z0>, TArr<A::Output, ATerm>> as FirstUnequal>::Output;
+}
+
+impl<V, A> CmpExtra<TArr<V, A>> for ATerm
+where
+    Z0: Cmp<V>,
+    ATerm: CmpExtra<A>,
+    TArr<Compare<Z0, V>, TArr<<ATerm as CmpExtra<A>>::Output, ATerm>>: FirstUnequal,
+{
+    type Output =
+        <TArr<Compare<Z0, V>, TArr<<ATerm as CmpExtra<A>>::Output, ATerm>> as FirstUnequal>::Output;
+}
+
+impl<V1, A1, V2, A2> CmpExtra<TArr<V2, A2>> for TArr<V1, A1>
+where
+    V1: Cmp<V2>,
+    A1: CmpExtra<A2>,
+    TArr<Compare<V1, V2>, TArr<A1::Output, ATerm>>: FirstUnequal,
+{
+    type Output = <TArr<Compare<V1, V2>, TArr<A1::Output, ATerm>> as FirstUnequal>::Output;
+}
+
+/// The first ordering in an array that is not `Equal`, or `Equal`.
+#[doc(hidden)]
+pub trait FirstUnequal {
+    type Output;
+}
+
+impl FirstUnequal for ATerm {
+    type Output = Equal;
+}
+
+impl<A> FirstUnequal for TArr<Less, A> {
+    type Output = Less;
+}
+
+impl<A> FirstUnequal for TArr<Greater, A> {
+    type Output = Greater;
+}
+
+impl<A: FirstUnequal> FirstUnequal for TArr<Equal, A> {
+    type Output = A::Output;
+}
+
+/// Implemented if a quantity named `Rhs` can be added to one named `Self`,
+/// that is if both are the same name, and so the same dimension.
+#[diagnostic::on_unimplemented(
+    message = "cannot add `{Rhs}` to `{Self}`",
+    label = "expected a quantity of dimension `{Self}`, found `{Rhs}`",
+    note = "quantities can only be added to quantities of the same dimension"
+)]
+pub trait AddableTo<Rhs> {}
+
+#[diagnostic::do_not_recommend]
+impl<N> AddableTo<N> for N {}
+
+// a second impl, which never applies, so that the name on the right is
+// compared once it is known rather than inferred from the one on the left,
+// which would report a mismatch as a type error without the message above
+#[diagnostic::do_not_recommend]
+impl<N> AddableTo<Never<N>> for N {}
+
+/// Implemented if a quantity named `Rhs` can be subtracted from one named
+/// `Self`, that is if both are the same name, and so the same dimension.
+#[diagnostic::on_unimplemented(
+    message = "cannot subtract `{Rhs}` from `{Self}`",
+    label = "expected a quantity of dimension `{Self}`, found `{Rhs}`",
+    note = "quantities can only be subtracted from quantities of the same dimension"
+)]
+pub trait SubtractableFrom<Rhs> {}
+
+#[diagnostic::do_not_recommend]
+impl<N> SubtractableFrom<N> for N {}
+
+#[diagnostic::do_not_recommend]
+impl<N> SubtractableFrom<Never<N>> for N {}
+
+/// Never a name, see the impls of [`AddableTo`].
+#[doc(hidden)]
+pub struct Never<N>(PhantomData<N>);
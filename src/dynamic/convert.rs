@@ -0,0 +1,248 @@
+use super::{
+    DimensionMismatch, DynQuantity,
+    parse::{self, ParseError, Scaled},
+};
+use crate::unit::{REGISTRY, registry::Registry};
+use core::{fmt, str::FromStr};
+
+/// Error returned by [`convert`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The value could not be parsed.
+    Value(ParseError),
+    /// The target unit could not be parsed.
+    Unit(ParseError),
+    /// The value and the target unit have different dimensions.
+    Dimension(DimensionMismatch),
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(err) => write!(f, "invalid value: {}", err),
+            Self::Unit(err) => write!(f, "invalid unit: {}", err),
+            Self::Dimension(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl core::error::Error for ConversionError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Value(err) | Self::Unit(err) => Some(err),
+            Self::Dimension(err) => Some(err),
+        }
+    }
+}
+
+/// Converts a quantity given as a string into the given unit.
+///
+/// Both the value and the target unit may use any unit of the
+/// [registry](crate::unit::REGISTRY), by symbol or by name, with or without
+/// prefix, combined with `*` (or `·`), `/`, `^` and parentheses. A quantity
+/// in a unit of a [kind](crate::unit::registry::UnitInfo::kind) converts to
+/// units of the same kind and to units without a kind (e.g. grays to `J/kg`),
+/// but not to units of another kind (e.g. grays to sieverts). Units with an
+/// offset convert absolute values (e.g. `300 K` is 26.85 °C).
+///
+/// # Examples
+///
+/// ```
+/// use danwi::dynamic::{ConversionError, convert};
+///
+/// assert_eq!(convert("72 km/s", "m/s"), Ok(72000.0));
+/// assert_eq!(convert("1 kg*m/s^2", "N"), Ok(1.0));
+/// assert_eq!(convert("10.5mV", "uV"), Ok(10500.0));
+/// assert_eq!(convert("2 kilometer", "mm"), Ok(2e6));
+///
+/// let err = convert("3 V", "A").unwrap_err();
+/// assert!(matches!(err, ConversionError::Dimension(_)));
+/// assert_eq!(
+///     err.to_string(),
+///     "dimension mismatch: expected ElectricCurrent (I), found Voltage (M·L²·T⁻³·I⁻¹)"
+/// );
+/// # #[cfg(feature = "radiological")] {
+/// assert_eq!(convert("1 Gy", "J/kg"), Ok(1.0));
+/// assert_eq!(
+///     convert("1 Gy", "Sv").unwrap_err().to_string(),
+///     "kind mismatch: expected EquivalentDose (L²·T⁻²), found AbsorbedDose (L²·T⁻²)"
+/// );
+/// # }
+/// ```
+pub fn convert(value: &str, unit: &str) -> Result<f64, ConversionError> {
+    convert_with(&REGISTRY, value, unit)
+}
+
+/// Converts a quantity given as a string into the given unit, resolving units
+/// against `registry`.
+pub fn convert_with(registry: &Registry, value: &str, unit: &str) -> Result<f64, ConversionError> {
+    let value = parse::parse_quantity(value, registry).map_err(ConversionError::Value)?;
+    convert_scaled(value, unit, registry)
+}
+
+/// Converts a quantity, such as the result of [`eval`](super::eval), into the
+/// given unit, like [`convert`].
+///
+/// # Examples
+///
+/// ```
+/// use danwi::dynamic::{convert_quantity, eval};
+///
+/// let speed = eval("100 m / 9.58 s").unwrap();
+/// assert_eq!(convert_quantity(speed, "km/h").unwrap().round(), 38.0);
+/// # #[cfg(feature = "thermal")]
+/// assert_eq!(convert_quantity(eval("300 K").unwrap(), "degC"), Ok(300.0 - 273.15));
+/// ```
+pub fn convert_quantity(quantity: DynQuantity<f64>, unit: &str) -> Result<f64, ConversionError> {
+    convert_scaled(Scaled::from_quantity(quantity), unit, &REGISTRY)
+}
+
+/// Converts a parsed value into the unit given as a string.
+fn convert_scaled(value: Scaled, unit: &str, registry: &Registry) -> Result<f64, ConversionError> {
+    let unit = parse::parse_unit(unit, registry).map_err(ConversionError::Unit)?;
+
+    unit.dimension()
+        .check(&value.dimension())
+        .map_err(ConversionError::Dimension)?;
+
+    Ok(value.ratio(unit))
+}
+
+/// Parses a unit expression into the value of one such unit in coherent SI
+/// base units.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::dynamic::parse_unit;
+///
+/// let unit = parse_unit("kN·m").unwrap();
+/// assert_eq!(unit.value(), 1000.0);
+/// assert_eq!(unit.dimension().name(), Some("Energy"));
+/// ```
+pub fn parse_unit(unit: &str) -> Result<DynQuantity<f64>, ParseError> {
+    parse::parse_unit(unit, &REGISTRY).map(Scaled::to_quantity)
+}
+
+impl FromStr for DynQuantity<f64> {
+    type Err = ParseError;
+
+    /// Parses a number followed by an optional unit expression, e.g. `72 km/s`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_quantity(s, &REGISTRY).map(Scaled::to_quantity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic::{ParseErrorKind, eval};
+
+    #[cfg(feature = "radiological")]
+    #[test]
+    fn kinds_convert_to_units_without_a_kind() {
+        assert_eq!(convert("1 Gy", "J/kg"), Ok(1.0));
+        assert_eq!(convert("2 J/kg", "mGy"), Ok(2000.0));
+        assert_eq!(convert("1 Bq", "1/s"), Ok(1.0));
+        assert_eq!(convert("1 rad", "1"), Ok(1.0));
+        assert!(matches!(
+            convert("1 Gy", "Sv"),
+            Err(ConversionError::Dimension(_))
+        ));
+    }
+
+    #[cfg(feature = "thermal")]
+    #[test]
+    fn offsets_convert_absolute_values() {
+        let near = |value: Result<f64, ConversionError>, expected: f64| {
+            assert!((value.unwrap() - expected).abs() < 1e-9, "{}", expected);
+        };
+
+        near(convert("300 K", "degC"), 26.85);
+        near(convert("20 degC", "K"), 293.15);
+        near(convert_quantity(eval("300 K").unwrap(), "degC"), 26.85);
+        near(
+            convert_quantity(eval("10 degC + 5 K").unwrap(), "degC"),
+            15.0,
+        );
+        near(
+            convert_quantity(eval("20 degC * 1 m").unwrap(), "K*m"),
+            20.0,
+        );
+    }
+
+    #[test]
+    fn convert_quantity_reports_the_unit_errors() {
+        let quantity = eval("1 m").unwrap();
+
+        assert!(matches!(
+            convert_quantity(quantity, "furlong"),
+            Err(ConversionError::Unit(_))
+        ));
+        assert!(matches!(
+            convert_quantity(quantity, "s"),
+            Err(ConversionError::Dimension(_))
+        ));
+        assert_eq!(convert_quantity(quantity, "mm"), Ok(1000.0));
+    }
+
+    #[test]
+    fn prefixes_of_powered_units_are_raised_to_the_power() {
+        let near = |value: Result<f64, ConversionError>, expected: f64| {
+            let value = value.unwrap();
+            assert!((value / expected - 1.0).abs() < 1e-12, "{}", value);
+        };
+
+        near(convert("1 cm2", "m2"), 1e-4);
+        near(convert("1 km3", "m3"), 1e9);
+        near(convert("1 m2", "mm2"), 1e6);
+        near(convert("1 km2", "m^2"), 1e6);
+        #[cfg(feature = "prefixes-common")]
+        near(convert("1 per_ms", "per_s"), 1e3);
+        #[cfg(all(feature = "fluid", feature = "prefixes-common"))]
+        near(convert("1 cm3", "mL"), 1.0);
+    }
+
+    #[test]
+    fn units_resolve_by_names_plurals_and_symbols() {
+        assert_eq!(convert("3 meters", "m"), Ok(3.0));
+        assert_eq!(convert("3 metres", "meter"), Ok(3.0));
+        assert_eq!(convert("2 kilometre", "m"), Ok(2000.0));
+        assert_eq!(convert("2 minutes", "s"), Ok(120.0));
+        #[cfg(feature = "electrical")]
+        {
+            assert_eq!(convert("2 ohm", "Ohm"), Ok(2.0));
+            assert_eq!(convert("2 Ω", "ohms"), Ok(2.0));
+        }
+        #[cfg(feature = "fluid")]
+        assert_eq!(convert("2 litres", "L"), Ok(2.0));
+    }
+
+    #[test]
+    fn prefixes_follow_the_policy_of_the_unit() {
+        let unknown = |value: &str| match convert(value, "1") {
+            Err(ConversionError::Value(err)) => {
+                assert_eq!(err.kind(), ParseErrorKind::UnknownUnit, "{}", value)
+            }
+            other => panic!("expected an unknown unit in `{}`, got {:?}", value, other),
+        };
+
+        // `prefixes=all` regardless of the features
+        assert_eq!(convert("1 cm", "m"), Ok(0.01));
+        assert_eq!(convert("1 dam", "m"), Ok(10.0));
+        // `prefixes=none`
+        unknown("1 kmin");
+        unknown("1 mh");
+        // the default policy
+        #[cfg(all(feature = "prefixes-common", not(feature = "prefixes-all")))]
+        unknown("1 cs");
+        #[cfg(feature = "prefixes-all")]
+        assert_eq!(convert("1 cs", "s"), Ok(0.01));
+        #[cfg(not(feature = "prefixes-common"))]
+        unknown("1 ms");
+        // binary prefixes only where the policy has them
+        unknown("1 Kis");
+        #[cfg(feature = "information")]
+        assert_eq!(convert("1 KiB", "B"), Ok(1024.0));
+    }
+}
@@ -0,0 +1,379 @@
+use super::{
+    DimensionMismatch, DynQuantity,
+    parse::{ParseError, ParseErrorKind, Parser, Scaled, Token, overflow},
+};
+use crate::unit::{REGISTRY, registry::Registry};
+use core::{fmt, ops::Range};
+
+/// Error returned by [`eval`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// The expression could not be parsed.
+    Parse(ParseError),
+    /// The operands of an addition or subtraction have different dimensions or
+    /// kinds.
+    Dimension {
+        /// Dimensions and kinds of the left (expected) and right (found)
+        /// operand.
+        mismatch: DimensionMismatch,
+        /// Byte range of the left operand.
+        lhs: Range<usize>,
+        /// Byte range of the right operand.
+        rhs: Range<usize>,
+    },
+}
+
+impl From<ParseError> for EvalError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::Dimension { mismatch, lhs, rhs } => write!(
+                f,
+                "cannot add or subtract {} ({}..{}) and {} ({}..{})",
+                mismatch.expected_dimension(),
+                lhs.start,
+                lhs.end,
+                mismatch.found_dimension(),
+                rhs.start,
+                rhs.end
+            ),
+        }
+    }
+}
+
+impl core::error::Error for EvalError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            Self::Dimension { mismatch, .. } => Some(mismatch),
+        }
+    }
+}
+
+/// Named constants that can be used in expressions.
+///
+/// The physical constants are those of [`danwi::f64`](crate::f64) and are
+/// only available with the features of their dimension.
+pub const CONSTANTS: &[(&str, DynQuantity<f64>)] = &[
+    // standard gravitational acceleration
+    #[cfg(all(feature = "f64", feature = "kinematic"))]
+    ("g0", constant(crate::f64::G_0)),
+    ("pi", DynQuantity::dimensionless(core::f64::consts::PI)),
+    // vacuum electric permittivity and magnetic permeability
+    #[cfg(all(feature = "f64", feature = "electrical"))]
+    ("eps0", constant(crate::f64::EPSILON_0)),
+    #[cfg(all(feature = "f64", feature = "magnetic"))]
+    ("mu0", constant(crate::f64::MU_0)),
+];
+
+/// Const version of `DynQuantity::from` for the constants, which are in
+/// coherent units.
+#[cfg(all(
+    feature = "f64",
+    any(feature = "kinematic", feature = "electrical", feature = "magnetic")
+))]
+const fn constant<D: crate::dimension::Dimensions>(
+    quantity: crate::quantity::Quantity<f64, D>,
+) -> DynQuantity<f64> {
+    use crate::dimension::ExtraExponents;
+
+    DynQuantity::new(quantity.value, D::EXPONENTS)
+        .with_extra_exponents(<D::X as ExtraExponents>::EXPONENTS)
+}
+
+/// Evaluates an arithmetic expression with units.
+///
+/// Expressions consist of numbers (optionally in scientific notation), units
+/// of the [registry](crate::unit::REGISTRY), [constants](CONSTANTS), `+`, `-`,
+/// `*` (or `·`), `/`, `^` with integer exponents, and parentheses. A number or
+/// unit directly followed by another one is multiplied with it and binds
+/// tighter than `*` and `/`, so `2 s` in `9 m / 2 s` is a single operand.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::dynamic::{EvalError, eval};
+///
+/// let v = eval("5 mA * 2 kOhm").unwrap();
+/// assert_eq!(v.to_string(), "10 V");
+///
+/// let x = eval("(3 m)^2 / 2 s").unwrap();
+/// assert_eq!(x.to_string(), "4.5 m²·s⁻¹");
+///
+/// let t = eval("1.5e3 ms + 2 s").unwrap();
+/// assert_eq!(t.to_string(), "3.5 s");
+///
+/// let w = eval("2 kg * g0").unwrap();
+/// assert_eq!(w.to_string(), "19.6133 N");
+///
+/// let err = eval("3 V + 2 A").unwrap_err();
+/// assert!(matches!(err, EvalError::Dimension { lhs, rhs, .. } if lhs == (0..3) && rhs == (6..9)));
+/// ```
+///
+/// Units of different [kinds](crate::unit::registry::UnitInfo::kind), such as
+/// the gray and the sievert, are kept apart, while the units of their
+/// dimension without a kind (e.g. `J/kg`) go with any of them:
+///
+/// ```
+/// # #[cfg(all(feature = "kinematic", feature = "radiological"))] {
+/// use danwi::dynamic::eval;
+///
+/// assert_eq!(eval("2 Gy * 3").unwrap().to_string(), "6 Gy");
+/// assert_eq!(eval("(3 m/s)^2").unwrap().to_string(), "9 m²·s⁻²");
+/// assert_eq!(eval("1 / 2 s").unwrap().to_string(), "0.5 s⁻¹");
+/// assert_eq!(eval("1 / 2 s + 1 Hz").unwrap().to_string(), "1.5 Hz");
+/// assert_eq!(
+///     eval("1 Gy + 1 Sv").unwrap_err().to_string(),
+///     "cannot add or subtract AbsorbedDose (L²·T⁻²) (0..4) and EquivalentDose (L²·T⁻²) (7..11)"
+/// );
+/// assert_eq!(eval("1 Gy + 1 J/kg").unwrap().to_string(), "2 Gy");
+/// assert_eq!(eval("1 rad + 1").unwrap().to_string(), "2 rad");
+/// # }
+/// ```
+pub fn eval(expr: &str) -> Result<DynQuantity<f64>, EvalError> {
+    eval_with(&REGISTRY, expr)
+}
+
+/// Evaluates an arithmetic expression with units, resolving units against
+/// `registry`.
+pub fn eval_with(registry: &Registry, expr: &str) -> Result<DynQuantity<f64>, EvalError> {
+    let mut evaluator = Evaluator {
+        parser: Parser::new(expr, registry),
+    };
+    let (value, _) = evaluator.expr()?;
+    evaluator.parser.lexer.expect_end()?;
+    Ok(value.to_quantity())
+}
+
+type Node = (Scaled, Range<usize>);
+
+struct Evaluator<'a> {
+    parser: Parser<'a>,
+}
+
+impl Evaluator<'_> {
+    /// expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Node, EvalError> {
+        let mut lhs = self.term()?;
+
+        loop {
+            let negate = if self.parser.lexer.eat(Token::Plus)? {
+                false
+            } else if self.parser.lexer.eat(Token::Minus)? {
+                true
+            } else {
+                return Ok(lhs);
+            };
+
+            let rhs = self.term()?;
+            let lhs_value = lhs.0.to_quantity();
+            let rhs_value = rhs.0.difference().to_quantity();
+
+            let value = if negate {
+                lhs_value.checked_sub(rhs_value)
+            } else {
+                lhs_value.checked_add(rhs_value)
+            }
+            .map_err(|mismatch| EvalError::Dimension {
+                mismatch,
+                lhs: lhs.1.clone(),
+                rhs: rhs.1.clone(),
+            })?;
+
+            lhs = (Scaled::from_quantity(value), lhs.1.start..rhs.1.end);
+        }
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Node, EvalError> {
+        let mut lhs = self.unary()?;
+
+        loop {
+            let divide = if self.parser.lexer.eat(Token::Star)? {
+                false
+            } else if self.parser.lexer.eat(Token::Slash)? {
+                true
+            } else {
+                return Ok(lhs);
+            };
+
+            let rhs = self.unary()?;
+            let span = lhs.1.start..rhs.1.end;
+            let value = if divide {
+                lhs.0.checked_div(rhs.0)
+            } else {
+                lhs.0.checked_mul(rhs.0)
+            };
+            lhs = (value.ok_or_else(|| overflow(span.clone()))?, span);
+        }
+    }
+
+    /// unary := '-' unary | product
+    fn unary(&mut self) -> Result<Node, EvalError> {
+        let (token, span) = self.parser.lexer.peek()?;
+
+        if token == Token::Minus {
+            self.parser.lexer.next()?;
+            let (value, inner) = self.unary()?;
+            let span = span.start..inner.end;
+            let value = Scaled::number(-1.0).checked_mul(value);
+            return Ok((value.ok_or_else(|| overflow(span.clone()))?, span));
+        }
+
+        self.product()
+    }
+
+    /// product := power power*
+    fn product(&mut self) -> Result<Node, EvalError> {
+        let mut lhs = self.power()?;
+
+        while matches!(
+            self.parser.lexer.peek()?.0,
+            Token::Number(_) | Token::Ident | Token::LParen
+        ) {
+            let rhs = self.power()?;
+            let span = lhs.1.start..rhs.1.end;
+            lhs = (
+                lhs.0
+                    .checked_mul(rhs.0)
+                    .ok_or_else(|| overflow(span.clone()))?,
+                span,
+            );
+        }
+
+        Ok(lhs)
+    }
+
+    /// power := atom ('^' integer)?
+    fn power(&mut self) -> Result<Node, EvalError> {
+        let (base, span) = self.atom()?;
+
+        if self.parser.lexer.eat(Token::Caret)? {
+            let exp = self.parser.exponent()?;
+            let span = span.start..self.parser.lexer.last_end();
+            let value = base
+                .checked_powi(exp)
+                .ok_or_else(|| overflow(span.clone()))?;
+            return Ok((value, span));
+        }
+
+        Ok((base, span))
+    }
+
+    /// atom := number | unit | '(' expr ')'
+    fn atom(&mut self) -> Result<Node, EvalError> {
+        match self.parser.lexer.next()? {
+            (Token::Number(value), span) => Ok((Scaled::number(value), span)),
+            (Token::Ident, span) => {
+                let name = self.parser.lexer.text(&span);
+
+                if let Some((_, constant)) = CONSTANTS.iter().find(|(n, _)| *n == name) {
+                    return Ok((Scaled::from_quantity(*constant), span));
+                }
+
+                Ok((self.parser.resolve(span.clone())?, span))
+            }
+            (Token::LParen, span) => {
+                let (value, _) = self.expr()?;
+                if !self.parser.lexer.eat(Token::RParen)? {
+                    let err = ParseError::new(ParseErrorKind::UnclosedParenthesis, span);
+                    return Err(err.into());
+                }
+                Ok((value, span.start..self.parser.lexer.last_end()))
+            }
+            (Token::End, span) => Err(ParseError::new(ParseErrorKind::UnexpectedEnd, span).into()),
+            (_, span) => Err(ParseError::new(ParseErrorKind::UnexpectedToken, span).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(expr: &str) -> ParseError {
+        match eval(expr) {
+            Err(EvalError::Parse(err)) => err,
+            other => panic!("expected a parse error for `{}`, got {:?}", expr, other),
+        }
+    }
+
+    #[test]
+    fn exponent_overflow_is_an_error_at_its_operands() {
+        for (expr, span) in [
+            ("(m^100)^2", 0..9),
+            ("m^100 * m^100", 0..13),
+            ("m127^100", 0..8),
+            ("2 m^100 m^100", 0..13),
+            ("1 / m^100 / m^100", 0..17),
+            ("-(m^-100 m^-100)", 2..15),
+        ] {
+            let err = parse_error(expr);
+            assert_eq!(err.kind(), ParseErrorKind::ExponentOverflow, "{}", expr);
+            assert_eq!(err.span(), span, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn exponents_up_to_the_limit_are_allowed() {
+        assert_eq!(
+            eval("m^100 * m^27").unwrap().exponents(),
+            [0, 127, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            eval("(m^-64)^2").unwrap().exponents(),
+            [0, -128, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(parse_error("m^101").kind(), ParseErrorKind::InvalidExponent);
+    }
+
+    #[test]
+    fn numbers_must_be_finite() {
+        for (expr, span) in [("1e400 m", 0..5), ("2 * 1e309", 4..9)] {
+            let err = parse_error(expr);
+            assert_eq!(err.kind(), ParseErrorKind::ExpectedNumber, "{}", expr);
+            assert_eq!(err.span(), span, "{}", expr);
+        }
+        assert_eq!(eval("1e308 m").unwrap().value(), 1e308);
+    }
+
+    #[cfg(feature = "thermal")]
+    #[test]
+    fn offsets_only_apply_to_absolute_values() {
+        let value = |expr| eval(expr).unwrap().value();
+
+        assert_eq!(value("20 degC"), 293.15);
+        assert_eq!(value("2 * 10 degC"), 293.15);
+        assert_eq!(value("20 degC + 5 K"), 298.15);
+        assert_eq!(value("20 degC + 5 degC"), 298.15);
+        assert_eq!(value("20 degC + 20 degC"), 313.15);
+        assert_eq!(value("30 degC - 10 degC"), 293.15);
+        assert_eq!(value("20 degC * 1 m"), 20.0);
+        assert_eq!(value("20 degC / (2 s)"), 10.0);
+    }
+
+    #[cfg(all(
+        feature = "f64",
+        feature = "kinematic",
+        feature = "electrical",
+        feature = "magnetic"
+    ))]
+    #[test]
+    fn constants_are_the_typed_ones() {
+        use crate::f64::{EPSILON_0, G_0, MU_0};
+
+        assert_eq!(eval("g0").unwrap(), DynQuantity::from(G_0));
+        assert_eq!(eval("eps0").unwrap(), DynQuantity::from(EPSILON_0));
+        assert_eq!(eval("mu0").unwrap(), DynQuantity::from(MU_0));
+
+        let one = eval("eps0 * mu0 * (299792458 m/s)^2").unwrap();
+        assert!((one.value() - 1.0).abs() < 1e-9);
+        assert_eq!(one.exponents(), [0; 7]);
+    }
+}
@@ -0,0 +1,643 @@
+//! Quantities whose dimension is only known at runtime.
+
+use crate::{
+    dimension::{
+        DimensionInfo, Dimensions, EXTRA_DIMENSIONS, ExtraExponents, write_product,
+        write_superscript,
+    },
+    quantity::Quantity,
+    scalar::Scalar,
+    unit::{REGISTRY, Unit},
+};
+use core::{
+    fmt,
+    ops::{Div, Mul, Neg},
+};
+
+mod convert;
+mod eval;
+mod parse;
+
+pub use convert::{ConversionError, convert, convert_quantity, convert_with, parse_unit};
+pub use eval::{CONSTANTS, EvalError, eval, eval_with};
+pub use parse::{ParseError, ParseErrorKind};
+
+/// Symbols of the SI base units, in the order of the dimension exponents.
+const BASE_SYMBOLS: [&str; 7] = ["s", "m", "kg", "A", "K", "mol", "cd"];
+
+/// A quantity whose dimension is stored at runtime.
+///
+/// The value is always expressed in coherent base units, and the dimension
+/// is stored as the exponents of the seven SI base dimensions in the order
+/// T, L, M, I, K, N, J and those of the additional base dimensions such as
+/// information (see [`Dimension`](crate::dimension::Dimension)).
+///
+/// Multiplication and division combine the exponents, while addition and
+/// subtraction are checked and fail when the dimensions differ.
+///
+/// A quantity in a unit of a [kind](crate::unit::registry::UnitInfo::kind)
+/// such as the gray, parsed or converted from a [`Quantity`], keeps it as long
+/// as it is multiplied or divided by plain numbers. Quantities of different
+/// kinds (e.g. grays and sieverts) are not added to each other, while a
+/// quantity without a kind (e.g. in `J/kg`) is added to any kind.
+///
+/// # Examples
+///
+/// ```
+/// # use danwi::prelude::*;
+/// use danwi::DynQuantity;
+///
+/// let v = DynQuantity::from(5.0.V());
+/// let i = DynQuantity::from(2.0.mA());
+///
+/// let r = v / i;
+/// assert_eq!(r.exponents(), [-3, 2, 1, -2, 0, 0, 0]);
+/// assert_eq!(r.to_string(), "2500 Ω");
+///
+/// let err = v.checked_add(i).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "dimension mismatch: expected Voltage (M·L²·T⁻³·I⁻¹), found ElectricCurrent (I)"
+/// );
+///
+/// let r = Ohms::try_from(r).unwrap();
+/// assert_eq!(r, 2.5.kOhm());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynQuantity<S: Scalar> {
+    value: S,
+    exponents: [i8; 7],
+    extra: [i8; EXTRA_DIMENSIONS],
+    kind: Option<&'static str>,
+}
+
+impl<S: Scalar> DynQuantity<S> {
+    /// Creates a quantity from a value in coherent SI base units and the
+    /// exponents of its dimension.
+    #[inline]
+    pub const fn new(value: S, exponents: [i8; 7]) -> Self {
+        Self {
+            value,
+            exponents,
+            extra: [0; EXTRA_DIMENSIONS],
+            kind: None,
+        }
+    }
+
+    /// Returns the quantity with the given exponents of the additional base
+    /// dimensions (e.g. `[1, 0, ...]` for information).
+    #[inline]
+    pub const fn with_extra_exponents(self, extra: [i8; EXTRA_DIMENSIONS]) -> Self {
+        Self { extra, ..self }
+    }
+
+    /// Returns the quantity as one of the given kind (e.g. `AbsorbedDose`), or
+    /// as a plain quantity of its dimension for `None`.
+    #[inline]
+    pub const fn with_kind(self, kind: Option<&'static str>) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// Creates a dimensionless quantity.
+    #[inline]
+    pub const fn dimensionless(value: S) -> Self {
+        Self::new(value, [0; 7])
+    }
+
+    /// Returns the value in coherent SI base units.
+    #[inline]
+    pub fn value(&self) -> S {
+        self.value
+    }
+
+    /// Returns the exponents of the SI base dimensions (T, L, M, I, K, N, J).
+    #[inline]
+    pub fn exponents(&self) -> [i8; 7] {
+        self.exponents
+    }
+
+    /// Returns the exponents of the additional base dimensions.
+    #[inline]
+    pub fn extra_exponents(&self) -> [i8; EXTRA_DIMENSIONS] {
+        self.extra
+    }
+
+    /// Returns the kind of the quantity, if it was given in a unit of a kind.
+    #[inline]
+    pub fn kind(&self) -> Option<&'static str> {
+        self.kind
+    }
+
+    /// Returns a runtime description of the dimension of the quantity.
+    #[inline]
+    pub const fn dimension(&self) -> DimensionInfo {
+        DimensionInfo::from_exponents_with_extra(self.exponents, self.extra)
+    }
+
+    /// Returns `true` if both quantities have the same dimension and kinds
+    /// that are the same or missing on either side.
+    #[inline]
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        let same_kind = match (self.kind, other.kind) {
+            (Some(kind), Some(other)) => kind == other,
+            _ => true,
+        };
+        self.exponents == other.exponents && self.extra == other.extra && same_kind
+    }
+
+    /// Returns `true` if the quantity is a plain number, without a kind.
+    fn is_number(&self) -> bool {
+        self.dimension().is_dimensionless() && self.kind.is_none()
+    }
+
+    /// Adds two quantities, failing if they are not
+    /// [compatible](Self::is_compatible). The sum has the kind of either.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, DimensionMismatch> {
+        self.check(&rhs)?;
+        Ok(Self {
+            value: self.value + rhs.value,
+            kind: self.kind.or(rhs.kind),
+            ..self
+        })
+    }
+
+    /// Subtracts two quantities, failing if they are not
+    /// [compatible](Self::is_compatible). The difference has the kind of
+    /// either.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, DimensionMismatch> {
+        self.check(&rhs)?;
+        Ok(Self {
+            value: self.value - rhs.value,
+            kind: self.kind.or(rhs.kind),
+            ..self
+        })
+    }
+
+    /// Multiplies two quantities, returning `None` if an exponent of the
+    /// dimension overflows.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let kind = match (self.is_number(), rhs.is_number()) {
+            (true, _) => rhs.kind,
+            (_, true) => self.kind,
+            _ => None,
+        };
+        Some(Self {
+            value: self.value * rhs.value,
+            exponents: zip_exponents(self.exponents, rhs.exponents, i8::checked_add)?,
+            extra: zip_exponents(self.extra, rhs.extra, i8::checked_add)?,
+            kind,
+        })
+    }
+
+    /// Divides two quantities, returning `None` if an exponent of the
+    /// dimension overflows.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            value: self.value / rhs.value,
+            exponents: zip_exponents(self.exponents, rhs.exponents, i8::checked_sub)?,
+            extra: zip_exponents(self.extra, rhs.extra, i8::checked_sub)?,
+            kind: if rhs.is_number() { self.kind } else { None },
+        })
+    }
+
+    /// Raises the quantity to an integer power, returning `None` if an
+    /// exponent of the dimension overflows.
+    pub fn checked_powi(self, n: i8) -> Option<Self> {
+        let mut exponents = self.exponents;
+        for exp in &mut exponents {
+            *exp = exp.checked_mul(n)?;
+        }
+        let mut extra = self.extra;
+        for exp in &mut extra {
+            *exp = exp.checked_mul(n)?;
+        }
+
+        let mut value = S::one();
+        for _ in 0..n.unsigned_abs() {
+            value = value * self.value;
+        }
+        if n < 0 {
+            value = S::one() / value;
+        }
+        Some(Self::new(value, exponents).with_extra_exponents(extra))
+    }
+
+    /// Raises the quantity to an integer power.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an exponent of the dimension overflows (see
+    /// [`checked_powi`](Self::checked_powi)).
+    pub fn powi(self, n: i8) -> Self {
+        self.checked_powi(n)
+            .expect("overflow in the exponents of a dimension")
+    }
+
+    /// Checks that `other` is [compatible](Self::is_compatible) with `self`,
+    /// returning the mismatch that `self` expects otherwise.
+    pub fn check(&self, other: &Self) -> Result<(), DimensionMismatch> {
+        if self.is_compatible(other) {
+            Ok(())
+        } else {
+            Err(DimensionMismatch {
+                expected: self.exponents,
+                found: other.exponents,
+                expected_extra: self.extra,
+                found_extra: other.extra,
+                expected_kind: self.kind,
+                found_kind: other.kind,
+            })
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if an exponent of the dimension overflows (see
+/// [`DynQuantity::checked_mul`]).
+impl<S: Scalar> Mul for DynQuantity<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("overflow in the exponents of a dimension")
+    }
+}
+
+/// # Panics
+///
+/// Panics if an exponent of the dimension overflows (see
+/// [`DynQuantity::checked_div`]).
+impl<S: Scalar> Div for DynQuantity<S> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("overflow in the exponents of a dimension")
+    }
+}
+
+impl<S: Scalar> Neg for DynQuantity<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            ..self
+        }
+    }
+}
+
+impl<S: Scalar, D: Dimensions> From<Quantity<S, D>> for DynQuantity<S> {
+    fn from(quantity: Quantity<S, D>) -> Self {
+        let value = quantity.unit.to_base(quantity.value);
+        Self::new(value, D::EXPONENTS)
+            .with_extra_exponents(<D::X as ExtraExponents>::EXPONENTS)
+            .with_kind(quantity.unit.kind)
+    }
+}
+
+impl<S: Scalar, D: Dimensions> TryFrom<DynQuantity<S>> for Quantity<S, D> {
+    type Error = DimensionMismatch;
+
+    fn try_from(quantity: DynQuantity<S>) -> Result<Self, Self::Error> {
+        let expected = D::EXPONENTS;
+        let expected_extra = <D::X as ExtraExponents>::EXPONENTS;
+
+        if quantity.exponents != expected || quantity.extra != expected_extra {
+            return Err(DimensionMismatch {
+                expected,
+                found: quantity.exponents,
+                expected_extra,
+                found_extra: quantity.extra,
+                expected_kind: None,
+                found_kind: quantity.kind,
+            });
+        }
+
+        let mut unit = Unit::base();
+        unit.kind = quantity.kind;
+        Ok(Quantity::with_unit(quantity.value, unit))
+    }
+}
+
+impl<S: Scalar> fmt::Display for DynQuantity<S> {
+    /// Formats the value followed by the
+    /// [symbol](crate::unit::registry::UnitInfo::display_symbol)
+    /// of the coherent unit of its dimension and kind in the
+    /// [registry](crate::unit::REGISTRY) (e.g. `10 V` or `20 m/s`), or by the
+    /// base units if there is none (e.g. `4.5 m²·s⁻¹` or `3 b·m`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)?;
+
+        let dimension = self.dimension();
+        let coherent = |dimension: DimensionInfo, kind| {
+            REGISTRY.iter().find(move |unit| {
+                unit.dimension() == dimension && unit.is_coherent() && unit.kind() == kind
+            })
+        };
+
+        if let Some(unit) = coherent(dimension, self.kind) {
+            return write!(f, " {}", unit.display_symbol());
+        }
+
+        if dimension.is_dimensionless() {
+            return Ok(());
+        }
+
+        write!(f, " ")?;
+        write_product(f, &self.exponents, &BASE_SYMBOLS)?;
+
+        // additional base dimensions by their coherent unit (e.g. the bit)
+        let mut first = self.exponents == [0; 7];
+        for (i, &exp) in self.extra.iter().enumerate().filter(|(_, exp)| **exp != 0) {
+            if !first {
+                write!(f, "·")?;
+            }
+            first = false;
+
+            let mut base = [0; EXTRA_DIMENSIONS];
+            base[i] = 1;
+            match coherent(DimensionInfo::from_exponents_with_extra([0; 7], base), None) {
+                Some(unit) => write!(f, "{}", unit.symbol())?,
+                None => write!(f, "X{}", i + 1)?,
+            }
+            write_superscript(f, exp)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Combines the exponents of two dimensions one by one with `op`, returning
+/// `None` if any of them overflows.
+fn zip_exponents<const N: usize>(
+    lhs: [i8; N],
+    rhs: [i8; N],
+    op: fn(i8, i8) -> Option<i8>,
+) -> Option<[i8; N]> {
+    let mut exponents = lhs;
+    for (exp, rhs) in exponents.iter_mut().zip(rhs) {
+        *exp = op(*exp, rhs)?;
+    }
+    Some(exponents)
+}
+
+/// Error returned when the dimensions or kinds of two quantities do not match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionMismatch {
+    /// Exponents of the SI base dimensions of the expected dimension.
+    pub expected: [i8; 7],
+    /// Exponents of the SI base dimensions of the dimension that was found
+    /// instead.
+    pub found: [i8; 7],
+    /// Exponents of the additional base dimensions of the expected dimension.
+    pub expected_extra: [i8; EXTRA_DIMENSIONS],
+    /// Exponents of the additional base dimensions of the dimension that was
+    /// found instead.
+    pub found_extra: [i8; EXTRA_DIMENSIONS],
+    /// Kind of the expected quantity (e.g. `AbsorbedDose`), if any.
+    pub expected_kind: Option<&'static str>,
+    /// Kind of the quantity that was found instead, if any.
+    pub found_kind: Option<&'static str>,
+}
+
+impl DimensionMismatch {
+    /// Returns the expected dimension, described by its kind if it has one
+    /// (e.g. `AbsorbedDose (L²·T⁻²)`) or else by its name.
+    pub fn expected_dimension(&self) -> impl fmt::Display + use<> {
+        Described(
+            DimensionInfo::from_exponents_with_extra(self.expected, self.expected_extra),
+            self.expected_kind,
+        )
+    }
+
+    /// Returns the dimension that was found, described like
+    /// [`expected_dimension`](Self::expected_dimension).
+    pub fn found_dimension(&self) -> impl fmt::Display + use<> {
+        Described(
+            DimensionInfo::from_exponents_with_extra(self.found, self.found_extra),
+            self.found_kind,
+        )
+    }
+}
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mismatch = if self.expected == self.found && self.expected_extra == self.found_extra {
+            "kind"
+        } else {
+            "dimension"
+        };
+
+        write!(
+            f,
+            "{} mismatch: expected {}, found {}",
+            mismatch,
+            self.expected_dimension(),
+            self.found_dimension()
+        )
+    }
+}
+
+/// Describes a dimension by its kind, if any, or by its name, followed by its
+/// base dimensions (e.g. `AbsorbedDose (L²·T⁻²)`).
+struct Described(DimensionInfo, Option<&'static str>);
+
+impl fmt::Display for Described {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(kind) = self.1 else {
+            return write!(f, "{}", self.0);
+        };
+
+        write!(f, "{} (", kind)?;
+        self.0.write_base_dimensions(f)?;
+        write!(f, ")")
+    }
+}
+
+impl core::error::Error for DimensionMismatch {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_operations_detect_exponent_overflow() {
+        let x = DynQuantity::new(2.0, [0, 100, 0, 0, 0, 0, 0]);
+
+        assert_eq!(x.checked_mul(x), None);
+        assert_eq!(x.checked_div(x.powi(-1)), None);
+        assert_eq!(x.checked_powi(2), None);
+        assert_eq!(x.checked_div(x), Some(DynQuantity::dimensionless(1.0)));
+        assert_eq!(
+            x.checked_powi(-1),
+            Some(DynQuantity::new(0.5, [0, -100, 0, 0, 0, 0, 0]))
+        );
+    }
+
+    #[test]
+    fn display_uses_the_display_symbols() {
+        assert_eq!(
+            DynQuantity::new(2.0, [0, 2, 0, 0, 0, 0, 0]).to_string(),
+            "2 m²"
+        );
+
+        #[cfg(feature = "kinematic")]
+        assert_eq!(
+            DynQuantity::new(1.0, [-1, 0, 0, 0, 0, 0, 0]).to_string(),
+            "1 s⁻¹"
+        );
+        #[cfg(feature = "kinematic")]
+        assert_eq!(
+            DynQuantity::new(1.0, [-1, 0, 0, 0, 0, 0, 0])
+                .with_kind(Some("Frequency"))
+                .to_string(),
+            "1 Hz"
+        );
+        #[cfg(feature = "kinematic")]
+        assert_eq!(
+            DynQuantity::new(20.0, [-1, 1, 0, 0, 0, 0, 0]).to_string(),
+            "20 m/s"
+        );
+        #[cfg(feature = "mechanical")]
+        assert_eq!(
+            DynQuantity::new(2.0, [-1, 1, 1, 0, 0, 0, 0]).to_string(),
+            "2 kg·m/s"
+        );
+        #[cfg(feature = "electrical")]
+        assert_eq!(
+            DynQuantity::new(10.0, [-3, 2, 1, -1, 0, 0, 0]).to_string(),
+            "10 V"
+        );
+        #[cfg(feature = "electrical")]
+        assert_eq!(
+            DynQuantity::new(3.0, [-3, 2, 1, -2, 0, 0, 0]).to_string(),
+            "3 Ω"
+        );
+        #[cfg(feature = "thermal")]
+        {
+            assert_eq!(
+                DynQuantity::new(1.0, [-2, 2, 1, 0, -1, 0, 0]).to_string(),
+                "1 J/K"
+            );
+            assert_eq!(
+                DynQuantity::new(1.0, [-3, 0, 1, 0, 0, 0, 0]).to_string(),
+                "1 W/m²"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic = "overflow in the exponents of a dimension"]
+    fn mul_panics_on_exponent_overflow() {
+        let x = DynQuantity::new(2.0, [0, 0, 0, 0, 0, 100, 0]);
+        let _ = x * x;
+    }
+
+    #[cfg(feature = "information")]
+    #[test]
+    fn information_is_an_additional_dimension() {
+        use crate::dynamic::{convert, eval};
+
+        #[cfg(feature = "prefixes-common")]
+        assert_eq!(convert("1 KiB", "kB"), Ok(1.024));
+        assert_eq!(convert("1 KiB", "B"), Ok(1024.0));
+        assert_eq!(convert("1 B/s", "b/s"), Ok(8.0));
+        assert_eq!(eval("1 MiB").unwrap().to_string(), "8388608 b");
+        assert_eq!(eval("1 B/s").unwrap().to_string(), "8 b/s");
+        assert_eq!(eval("3 b * 2 m").unwrap().to_string(), "6 m·b");
+        assert_eq!(eval("1 b/m^2").unwrap().to_string(), "1 m⁻²·b");
+        assert_eq!(
+            convert("1 b", "m").unwrap_err().to_string(),
+            "dimension mismatch: expected Length (L), found Information (X1)"
+        );
+        assert_eq!(
+            eval("1 b + 1").unwrap_err().to_string(),
+            "cannot add or subtract Information (X1) (0..3) and Dimensionless (1) (6..7)"
+        );
+    }
+
+    #[cfg(all(feature = "information", feature = "f64"))]
+    #[test]
+    fn information_quantities_convert_from_and_into_typed_ones() {
+        use crate::{dimension::Information, unit::constants::KiB};
+
+        let size = DynQuantity::from(2.0 * KiB);
+        assert_eq!(size.value(), 16384.0);
+        assert_eq!(size.extra_exponents()[0], 1);
+        assert_eq!(Quantity::<f64, Information>::try_from(size), Ok(2.0 * KiB));
+        assert!(Quantity::<f64, crate::dimension::Length>::try_from(size).is_err());
+    }
+
+    #[cfg(all(feature = "radiological", feature = "f64"))]
+    #[test]
+    fn kinds_only_mismatch_other_kinds() {
+        use crate::unit::constants::{Gy, J, Sv, kg, mGy};
+
+        let dose = DynQuantity::from(2.0 * mGy);
+        assert_eq!(dose.kind(), Some("AbsorbedDose"));
+        assert_eq!(DynQuantity::from(2.0 * (J / kg)).kind(), None);
+        assert_eq!(
+            DynQuantity::from((2.0 * Gy) * 2.0).kind(),
+            Some("AbsorbedDose")
+        );
+
+        let plain = DynQuantity::from(1.0 * (J / kg));
+        let sum = dose.checked_add(plain).unwrap();
+        assert_eq!(sum.kind(), Some("AbsorbedDose"));
+        assert_eq!(
+            plain.checked_add(dose).unwrap().kind(),
+            Some("AbsorbedDose")
+        );
+        assert!(dose.is_compatible(&plain) && plain.is_compatible(&dose));
+
+        let err = dose.checked_sub(DynQuantity::from(1.0 * Sv)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "kind mismatch: expected AbsorbedDose (L²·T⁻²), found EquivalentDose (L²·T⁻²)"
+        );
+
+        let dose = Quantity::<f64, crate::dimension::AbsorbedDose>::try_from(dose).unwrap();
+        assert_eq!(DynQuantity::from(dose).kind(), Some("AbsorbedDose"));
+    }
+
+    #[cfg(feature = "radiological")]
+    #[test]
+    fn kinds_go_with_quantities_without_a_kind() {
+        use crate::dynamic::eval;
+
+        assert_eq!(eval("1 rad + 1").unwrap().to_string(), "2 rad");
+        assert_eq!(eval("1 + 1 rad").unwrap().to_string(), "2 rad");
+        assert_eq!(eval("1 Gy + 1 m^2/s^2").unwrap().to_string(), "2 Gy");
+        assert_eq!(eval("3 Bq - 1 / s").unwrap().to_string(), "2 Bq");
+        assert!(eval("1 Gy + 1 Sv").is_err());
+        assert!(eval("1 rad + 1 sr").is_err());
+    }
+
+    #[cfg(all(
+        feature = "kinematic",
+        feature = "radiological",
+        feature = "photometric"
+    ))]
+    #[test]
+    fn frequency_and_luminous_intensity_are_kinds() {
+        use crate::dynamic::{ConversionError, convert, eval};
+
+        assert_eq!(
+            eval("3 Bq - 1 Hz").unwrap_err().to_string(),
+            "cannot add or subtract Activity (T⁻¹) (0..4) and Frequency (T⁻¹) (7..11)"
+        );
+        assert!(eval("1 cd + 1 lm").is_err());
+        assert_eq!(
+            convert("1 cd", "lm").unwrap_err().to_string(),
+            "kind mismatch: expected LuminousFlux (J), found LuminousIntensity (J)"
+        );
+        assert!(matches!(
+            convert("1 Hz", "Bq"),
+            Err(ConversionError::Dimension(_))
+        ));
+        assert_eq!(eval("2 kHz").unwrap().to_string(), "2000 Hz");
+        assert_eq!(convert("1 kHz", "1/s"), Ok(1000.0));
+    }
+}
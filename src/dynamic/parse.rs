@@ -0,0 +1,595 @@
+//! Parsing of quantities and unit expressions such as `72 km/s` or
+//! `kg·m/s^2`.
+
+use super::DynQuantity;
+use crate::{
+    dimension::EXTRA_DIMENSIONS,
+    unit::registry::{Registry, UnitInfo},
+};
+use core::{fmt, ops::Range};
+
+/// Error returned when a quantity or unit expression cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: Range<usize>,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+
+    /// Returns the kind of error.
+    #[inline]
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte range of the input the error refers to.
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Kinds of [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A character that is not part of any token.
+    InvalidCharacter,
+    /// A number was expected.
+    ExpectedNumber,
+    /// A unit was expected.
+    ExpectedUnit,
+    /// An identifier that is not a known unit.
+    UnknownUnit,
+    /// An exponent that is not a small integer.
+    InvalidExponent,
+    /// An exponent of the resulting dimension that is out of range (e.g. in
+    /// `(m^100)^2`).
+    ExponentOverflow,
+    /// A `(` without matching `)`.
+    UnclosedParenthesis,
+    /// A token that is not allowed at this position.
+    UnexpectedToken,
+    /// The input ended unexpectedly.
+    UnexpectedEnd,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::InvalidCharacter => "invalid character",
+            Self::ExpectedNumber => "expected a number",
+            Self::ExpectedUnit => "expected a unit",
+            Self::UnknownUnit => "unknown unit",
+            Self::InvalidExponent => "exponent must be an integer",
+            Self::ExponentOverflow => "exponent of the dimension is out of range",
+            Self::UnclosedParenthesis => "unclosed parenthesis",
+            Self::UnexpectedToken => "unexpected token",
+            Self::UnexpectedEnd => "unexpected end of input",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Token {
+    Number(f64),
+    Ident,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LParen,
+    RParen,
+    End,
+}
+
+/// Splits the input into tokens, keeping one token of lookahead.
+pub(crate) struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+    last_end: usize,
+    peeked: Option<(Token, Range<usize>)>,
+}
+
+impl<'a> Lexer<'a> {
+    pub(crate) fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            last_end: 0,
+            peeked: None,
+        }
+    }
+
+    pub(crate) fn text(&self, span: &Range<usize>) -> &'a str {
+        &self.src[span.clone()]
+    }
+
+    pub(crate) fn peek(&mut self) -> Result<(Token, Range<usize>), ParseError> {
+        if let Some(peeked) = &self.peeked {
+            return Ok(peeked.clone());
+        }
+
+        let next = self.lex()?;
+        self.peeked = Some(next.clone());
+        Ok(next)
+    }
+
+    pub(crate) fn next(&mut self) -> Result<(Token, Range<usize>), ParseError> {
+        let next = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.lex()?,
+        };
+        self.last_end = next.1.end;
+        Ok(next)
+    }
+
+    /// Returns the end of the last consumed token.
+    pub(crate) fn last_end(&self) -> usize {
+        self.last_end
+    }
+
+    /// Consumes the next token if it is `token`.
+    pub(crate) fn eat(&mut self, token: Token) -> Result<bool, ParseError> {
+        if self.peek()?.0 == token {
+            self.next()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub(crate) fn expect_end(&mut self) -> Result<(), ParseError> {
+        match self.next()? {
+            (Token::End, _) => Ok(()),
+            (_, span) => Err(ParseError::new(ParseErrorKind::UnexpectedToken, span)),
+        }
+    }
+
+    fn lex(&mut self) -> Result<(Token, Range<usize>), ParseError> {
+        let rest = &self.src[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+
+        let start = self.pos;
+        let Some(c) = trimmed.chars().next() else {
+            return Ok((Token::End, start..start));
+        };
+
+        let token = match c {
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' | '·' | '×' => Token::Star,
+            '/' => Token::Slash,
+            '^' => Token::Caret,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '0'..='9' | '.' => {
+                let len = number_len(trimmed);
+                let span = start..start + len;
+                // literals too large for an `f64` parse as infinity
+                let value = trimmed[..len]
+                    .parse()
+                    .ok()
+                    .filter(|value: &f64| value.is_finite())
+                    .ok_or_else(|| ParseError::new(ParseErrorKind::ExpectedNumber, span.clone()))?;
+                self.pos = span.end;
+                return Ok((Token::Number(value), span));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let len = trimmed
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(trimmed.len());
+                self.pos += len;
+                return Ok((Token::Ident, start..self.pos));
+            }
+            _ => {
+                let span = start..start + c.len_utf8();
+                return Err(ParseError::new(ParseErrorKind::InvalidCharacter, span));
+            }
+        };
+
+        self.pos += c.len_utf8();
+        Ok((token, start..self.pos))
+    }
+}
+
+/// Returns the length of the decimal number (with optional fraction and
+/// exponent) at the start of `s`.
+fn number_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let digits = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+
+    let mut len = digits(0);
+
+    if bytes.get(len) == Some(&b'.') {
+        len = digits(len + 1);
+    }
+
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let mut exp = len + 1;
+        if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+            exp += 1;
+        }
+        if bytes.get(exp).is_some_and(u8::is_ascii_digit) {
+            len = digits(exp);
+        }
+    }
+
+    len
+}
+
+/// A quantity whose value is kept as a mantissa and a separate power of ten, so
+/// that prefixes cancel out exactly (e.g. `mV` to `uV`).
+///
+/// The offset of a unit such as the degree Celsius is only kept as long as it
+/// is multiplied by plain numbers, so that e.g. `20 degC` is 293.15 K while
+/// `degC/s` is a rate of change. On the right of an addition or subtraction,
+/// the offset is dropped as well, so that `20 degC + 5 degC` is 298.15 K.
+///
+/// The kind of a unit such as the gray is kept the same way, so that `2 Gy` is
+/// not a dose equivalent while `Gy/s` is only a dimension, which goes with any
+/// kind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Scaled {
+    pub(crate) value: f64,
+    pub(crate) exp10: i32,
+    pub(crate) offset: f64,
+    pub(crate) exponents: [i8; 7],
+    pub(crate) extra: [i8; EXTRA_DIMENSIONS],
+    pub(crate) kind: Option<&'static str>,
+}
+
+impl Scaled {
+    pub(crate) const fn number(value: f64) -> Self {
+        Self {
+            value,
+            exp10: 0,
+            offset: 0.0,
+            exponents: [0; 7],
+            extra: [0; EXTRA_DIMENSIONS],
+            kind: None,
+        }
+    }
+
+    /// Returns the value with the exponents of the SI and additional base
+    /// dimensions of `quantity`, without offset, and with the kind of
+    /// `quantity`.
+    pub(crate) fn from_quantity(quantity: DynQuantity<f64>) -> Self {
+        Self {
+            value: quantity.value(),
+            exp10: 0,
+            offset: 0.0,
+            exponents: quantity.exponents(),
+            extra: quantity.extra_exponents(),
+            kind: quantity.kind(),
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        self.dimension().is_number() && self.offset == 0.0
+    }
+
+    /// Returns the mantissa of `self` as a quantity of its dimension and kind.
+    pub(crate) fn dimension(self) -> DynQuantity<f64> {
+        DynQuantity::new(self.value, self.exponents)
+            .with_extra_exponents(self.extra)
+            .with_kind(self.kind)
+    }
+
+    /// Raises `self` to an integer power, returning `None` if an exponent
+    /// overflows.
+    pub(crate) fn checked_powi(self, n: i8) -> Option<Self> {
+        let quantity = self.dimension().checked_powi(n)?;
+        Some(Self {
+            exp10: self.exp10.checked_mul(n as i32)?,
+            ..Self::from_quantity(quantity)
+        })
+    }
+
+    /// Multiplies two values, returning `None` if an exponent overflows.
+    pub(crate) fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let quantity = self.dimension().checked_mul(rhs.dimension())?;
+        let offset = match (self.is_number(), rhs.is_number()) {
+            (true, _) => rhs.offset,
+            (_, true) => self.offset,
+            _ => 0.0,
+        };
+        Some(Self {
+            exp10: self.exp10.checked_add(rhs.exp10)?,
+            offset,
+            ..Self::from_quantity(quantity)
+        })
+    }
+
+    /// Divides two values, returning `None` if an exponent overflows.
+    pub(crate) fn checked_div(self, rhs: Self) -> Option<Self> {
+        let quantity = self.dimension().checked_div(rhs.dimension())?;
+        Some(Self {
+            exp10: self.exp10.checked_sub(rhs.exp10)?,
+            offset: if rhs.is_number() { self.offset } else { 0.0 },
+            ..Self::from_quantity(quantity)
+        })
+    }
+
+    /// Returns the value of `self` expressed in multiples of `unit`.
+    pub(crate) fn ratio(self, unit: Self) -> f64 {
+        let ratio = self.value / unit.value * libm::exp10((self.exp10 - unit.exp10) as f64);
+        ratio + (self.offset - unit.offset) / unit.value * libm::exp10(-unit.exp10 as f64)
+    }
+
+    /// Returns `self` as a difference of values, without the offset of its
+    /// unit (e.g. `5 degC` as 5 K rather than 278.15 K).
+    pub(crate) fn difference(self) -> Self {
+        Self {
+            offset: 0.0,
+            ..self
+        }
+    }
+
+    pub(crate) fn to_quantity(self) -> DynQuantity<f64> {
+        let value = self.value * libm::exp10(self.exp10 as f64) + self.offset;
+        DynQuantity::new(value, self.exponents)
+            .with_extra_exponents(self.extra)
+            .with_kind(self.kind)
+    }
+}
+
+/// Returns the error for an operation whose result has an exponent out of
+/// range, at the `span` of its operands.
+pub(crate) fn overflow(span: Range<usize>) -> ParseError {
+    ParseError::new(ParseErrorKind::ExponentOverflow, span)
+}
+
+/// Parses unit expressions against the units of a [`Registry`].
+pub(crate) struct Parser<'a> {
+    pub(crate) lexer: Lexer<'a>,
+    registry: &'a Registry,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(src: &'a str, registry: &'a Registry) -> Self {
+        Self {
+            lexer: Lexer::new(src),
+            registry,
+        }
+    }
+
+    /// Returns `true` if the next token can start a unit.
+    pub(crate) fn at_unit(&mut self) -> Result<bool, ParseError> {
+        Ok(matches!(self.lexer.peek()?.0, Token::Ident | Token::LParen))
+    }
+
+    /// unit_expr := unit_pow (('*' | '/')? unit_pow)*
+    pub(crate) fn unit_expr(&mut self) -> Result<Scaled, ParseError> {
+        let start = self.lexer.peek()?.1.start;
+        let mut lhs = self.unit_pow()?;
+
+        loop {
+            let result = if self.lexer.eat(Token::Star)? {
+                lhs.checked_mul(self.unit_pow()?)
+            } else if self.lexer.eat(Token::Slash)? {
+                lhs.checked_div(self.unit_pow()?)
+            } else if self.at_unit()? {
+                lhs.checked_mul(self.unit_pow()?)
+            } else {
+                return Ok(lhs);
+            };
+            lhs = result.ok_or_else(|| overflow(start..self.lexer.last_end()))?;
+        }
+    }
+
+    /// unit_pow := unit_atom ('^' integer)?
+    fn unit_pow(&mut self) -> Result<Scaled, ParseError> {
+        let start = self.lexer.peek()?.1.start;
+        let base = self.unit_atom()?;
+
+        if self.lexer.eat(Token::Caret)? {
+            let exp = self.exponent()?;
+            base.checked_powi(exp)
+                .ok_or_else(|| overflow(start..self.lexer.last_end()))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// unit_atom := identifier | '(' unit_expr ')' | '1'
+    fn unit_atom(&mut self) -> Result<Scaled, ParseError> {
+        match self.lexer.next()? {
+            (Token::Ident, span) => self.resolve(span),
+            (Token::LParen, span) => {
+                let inner = self.unit_expr()?;
+                if !self.lexer.eat(Token::RParen)? {
+                    return Err(ParseError::new(ParseErrorKind::UnclosedParenthesis, span));
+                }
+                Ok(inner)
+            }
+            (Token::Number(1.0), _) => Ok(Scaled::number(1.0)),
+            (_, span) => Err(ParseError::new(ParseErrorKind::ExpectedUnit, span)),
+        }
+    }
+
+    /// integer := ('+' | '-')? digits | '(' integer ')'
+    pub(crate) fn exponent(&mut self) -> Result<i8, ParseError> {
+        let (token, span) = self.lexer.next()?;
+
+        let exp = match token {
+            Token::LParen => {
+                let exp = self.exponent()?;
+                if !self.lexer.eat(Token::RParen)? {
+                    return Err(ParseError::new(ParseErrorKind::UnclosedParenthesis, span));
+                }
+                return Ok(exp);
+            }
+            Token::Plus => return self.exponent(),
+            Token::Minus => return self.exponent().map(|exp| -exp),
+            Token::Number(exp) => exp,
+            _ => return Err(ParseError::new(ParseErrorKind::InvalidExponent, span)),
+        };
+
+        if !(-100.0..=100.0).contains(&exp) || exp as i8 as f64 != exp {
+            return Err(ParseError::new(ParseErrorKind::InvalidExponent, span));
+        }
+
+        Ok(exp as i8)
+    }
+
+    /// Resolves an identifier such as `kOhm`, `kilometer` or `cm2` to the
+    /// value of one such unit in coherent SI base units.
+    pub(crate) fn resolve(&self, span: Range<usize>) -> Result<Scaled, ParseError> {
+        let ident = self.lexer.text(&span);
+
+        if let Some(unit) = resolve(self.registry, ident) {
+            return Ok(unit);
+        }
+
+        // trailing digits are an exponent (e.g. `cm2`)
+        let base = ident.trim_end_matches(|c: char| c.is_ascii_digit());
+        if !base.is_empty()
+            && base.len() < ident.len()
+            && let Ok(exp) = ident[base.len()..].parse::<i8>()
+            && let Some(unit) = resolve(self.registry, base)
+        {
+            return unit.checked_powi(exp).ok_or_else(|| overflow(span));
+        }
+
+        Err(ParseError::new(ParseErrorKind::UnknownUnit, span))
+    }
+}
+
+/// Finds a unit by its symbol or name, optionally with a prefix.
+fn resolve(registry: &Registry, ident: &str) -> Option<Scaled> {
+    let unit = |unit: &UnitInfo, prefix: i8, factor: f64| Scaled {
+        value: unit.factor() * libm::pow(factor, unit.power() as f64),
+        exp10: (prefix * unit.power() + unit.scale()) as i32,
+        offset: unit.offset(),
+        exponents: unit.dimension().exponents(),
+        extra: unit.dimension().extra_exponents(),
+        kind: unit.kind(),
+    };
+
+    if let Some(info) = registry
+        .by_symbol(ident)
+        .or_else(|| registry.by_name(ident))
+    {
+        return Some(unit(info, 0, 1.0));
+    }
+
+    registry.iter().find_map(|info| {
+        // the prefix of a powered unit goes before its base unit (e.g. `per_ms`
+        // and `square_centimeter`)
+        let (symbol_at, name_at) = if info.power() == 1 {
+            (0, 0)
+        } else {
+            (
+                if info.symbol().starts_with("per_") {
+                    4
+                } else {
+                    0
+                },
+                info.name().rfind('_').map_or(0, |i| i + 1),
+            )
+        };
+
+        info.prefixes()
+            .iter()
+            .chain(info.binary_prefixes())
+            .find_map(|prefix| {
+                let prefix_symbols = || {
+                    [prefix.symbol()]
+                        .into_iter()
+                        .chain(prefix.aliases().iter().copied())
+                };
+
+                let by_symbol = [info.symbol()].iter().chain(info.symbols()).any(|symbol| {
+                    prefix_symbols().any(|p| is_prefixed(ident, p, symbol, symbol_at))
+                });
+                let by_name = [info.name()]
+                    .iter()
+                    .chain(info.names())
+                    .any(|name| is_prefixed(ident, prefix.name(), name, name_at));
+
+                (by_symbol || by_name).then(|| unit(info, prefix.exponent(), prefix.factor()))
+            })
+    })
+}
+
+/// Returns whether `ident` is `unit` with `prefix` inserted at byte `at`.
+fn is_prefixed(ident: &str, prefix: &str, unit: &str, at: usize) -> bool {
+    let (head, tail) = unit.split_at(at);
+    ident
+        .strip_prefix(head)
+        .and_then(|rest| rest.strip_prefix(prefix))
+        == Some(tail)
+}
+
+/// Parses a unit expression such as `kg·m/s^2` into the value of one such unit
+/// in coherent SI base units.
+pub(crate) fn parse_unit(src: &str, registry: &Registry) -> Result<Scaled, ParseError> {
+    let mut parser = Parser::new(src, registry);
+    let unit = parser.unit_expr()?;
+    parser.lexer.expect_end()?;
+    Ok(unit)
+}
+
+/// Parses a number optionally followed by a unit expression, e.g. `72 km/s`
+/// or `-10.5mV`.
+pub(crate) fn parse_quantity(src: &str, registry: &Registry) -> Result<Scaled, ParseError> {
+    let mut parser = Parser::new(src, registry);
+    let negative = parser.lexer.eat(Token::Minus)?;
+
+    let value = match parser.lexer.next()? {
+        (Token::Number(value), _) => value,
+        (_, span) => return Err(ParseError::new(ParseErrorKind::ExpectedNumber, span)),
+    };
+    let value = if negative { -value } else { value };
+
+    let quantity = if parser.lexer.peek()?.0 == Token::End {
+        Scaled::number(value)
+    } else {
+        let start = parser.lexer.peek()?.1.start;
+        let unit = parser.unit_expr()?;
+        Scaled::number(value)
+            .checked_mul(unit)
+            .ok_or_else(|| overflow(start..parser.lexer.last_end()))?
+    };
+
+    parser.lexer.expect_end()?;
+    Ok(quantity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unit::REGISTRY;
+
+    #[test]
+    fn exponent_overflow_in_units_is_an_error() {
+        let err = parse_unit("s (m^100)^2", &REGISTRY).unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::ExponentOverflow);
+        assert_eq!(err.span(), 2..11);
+
+        let err = parse_quantity("3 m^100/m^-100", &REGISTRY).unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::ExponentOverflow);
+        assert_eq!(err.span(), 2..14);
+    }
+}
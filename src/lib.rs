@@ -1,51 +1,120 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+mod macros;
+
 pub mod dimension;
+pub mod dynamic;
 pub mod quantity;
 pub mod scalar;
 pub mod unit;
 
+#[doc(hidden)]
+pub mod __private {
+    pub use paste::paste;
+    pub use typenum;
+}
+
+pub use dynamic::DynQuantity;
 pub use quantity::Quantity;
-pub use scalar::Scalar;
-pub use unit::{
-    Unit,
-    ext::{F32QuantityExt, F64QuantityExt},
-};
+pub use scalar::{Scalar, ScalarExt};
+pub use unit::Unit;
+#[cfg(all(feature = "ext-methods", feature = "f32"))]
+pub use unit::ext::F32QuantityExt;
+#[cfg(all(feature = "ext-methods", feature = "f64"))]
+pub use unit::ext::F64QuantityExt;
+#[cfg(feature = "ext-methods")]
+pub use unit::ext::QuantityExt;
 
 #[cfg(feature = "f32")]
 pub mod f32 {
+    #[doc(inline)]
+    pub use crate::q_f32 as q;
+    #[cfg(feature = "ext-methods")]
+    pub use crate::unit::ext::F32QuantityExt as QuantityExt;
     pub use crate::{
-        F32QuantityExt as QuantityExt, dimension,
+        dimension,
         quantity::Quantity,
-        scalar::Scalar,
+        scalar::{Scalar, ScalarExt},
         unit::{Unit, constants, types::f32 as types},
     };
 
     /// Standard gravitational acceleration (9.80665 m/s²).
+    #[cfg(feature = "kinematic")]
     pub const G_0: Quantity<f32, dimension::Acceleration> = Quantity::from_f32(9.80665);
+
+    /// Vacuum electric permittivity ε₀ (8.8541878188 × 10⁻¹² F/m, CODATA 2022).
+    #[cfg(feature = "electrical")]
+    pub const EPSILON_0: Quantity<f32, dimension::Permittivity> = Quantity::from_f32(8.854188e-12);
+
+    /// Vacuum magnetic permeability μ₀ (1.25663706127 × 10⁻⁶ H/m, CODATA 2022).
+    #[cfg(feature = "magnetic")]
+    pub const MU_0: Quantity<f32, dimension::Permeability> = Quantity::from_f32(1.256637e-6);
 }
 
 #[cfg(feature = "f64")]
 pub mod f64 {
+    #[cfg(feature = "ext-methods")]
+    pub use crate::unit::ext::F64QuantityExt as QuantityExt;
     pub use crate::{
-        F64QuantityExt as QuantityExt, dimension,
+        dimension, q,
         quantity::Quantity,
-        scalar::Scalar,
+        scalar::{Scalar, ScalarExt},
         unit::{Unit, constants, types::f64 as types},
     };
 
     /// Standard gravitational acceleration (9.80665 m/s²).
+    #[cfg(feature = "kinematic")]
     pub const G_0: Quantity<f64, dimension::Acceleration> = Quantity::from_f64(9.80665);
+
+    /// Vacuum electric permittivity ε₀ (8.8541878188 × 10⁻¹² F/m, CODATA 2022).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use danwi::f64::{EPSILON_0, MU_0, QuantityExt};
+    ///
+    /// // ε₀·μ₀·c² = 1
+    /// let c = 299_792_458.0.mps();
+    /// assert!(((EPSILON_0 * MU_0 * c * c).value() - 1.0).abs() < 1e-9);
+    /// assert_eq!(EPSILON_0, 8.8541878188.pFpm());
+    /// ```
+    #[cfg(feature = "electrical")]
+    pub const EPSILON_0: Quantity<f64, dimension::Permittivity> =
+        Quantity::from_f64(8.8541878188e-12);
+
+    /// Vacuum magnetic permeability μ₀ (1.25663706127 × 10⁻⁶ H/m, CODATA 2022).
+    #[cfg(feature = "magnetic")]
+    pub const MU_0: Quantity<f64, dimension::Permeability> = Quantity::from_f64(1.25663706127e-6);
 }
 
+/// The `f64` quantities, units and extension methods, for glob imports.
+///
+/// The unit constants are left out, since a glob-imported constant such as
+/// `min` or `rad` turns locals of the same name into patterns; import them by
+/// name from [`constants`](crate::unit::constants).
+///
+/// # Examples
+///
+/// ```
+/// use danwi::prelude::*;
+/// use danwi::prelude::constants::km;
+///
+/// let (min, max) = (1.0.m(), 2.0.m());
+/// assert_eq!(min + max, 0.003 * km);
+///
+/// let h = 2.0.hours();
+/// assert_eq!(h, 120.0.minutes());
+/// ```
 #[cfg(feature = "f64")]
 pub mod prelude {
+    #[cfg(feature = "ext-methods")]
+    pub use crate::unit::ext::F64QuantityExt as QuantityExt;
     pub use crate::{
-        F64QuantityExt as QuantityExt,
+        q,
         quantity::Quantity,
-        scalar::Scalar,
-        unit::{Unit, constants::*, types::f64::*},
+        scalar::{Scalar, ScalarExt},
+        unit::{Unit, constants, types::f64::*},
     };
 }
@@ -0,0 +1,370 @@
+/// Creates an `f64` quantity from a number and a unit.
+///
+/// The unit is resolved at compile time with [`unit!`], so it may be a single
+/// symbol or a compound unit such as `m/s^2`, and a misspelled symbol is a
+/// compile error. Use [`f32::q`](crate::f32::q) for `f32` quantities.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{f64::types::*, q};
+///
+/// let r: Ohms = q!(4.7 kOhm);
+/// let t = q!(250 ms);
+/// let i = q!(-1.5e-3 A);
+/// let a = q!(9.81 m/s^2);
+///
+/// assert_eq!(r.value(), 4.7);
+/// assert_eq!(t, q!(0.25 s));
+/// assert_eq!(r * i, q!(-7.05 V));
+/// assert_eq!(a, q!(9.81 mps2));
+/// ```
+///
+/// ```compile_fail
+/// let r = danwi::q!(4.7 kOhms);
+/// ```
+///
+/// ```compile_fail
+/// let r: danwi::f64::types::Ohms = danwi::q!(4.7 kV);
+/// ```
+#[macro_export]
+macro_rules! q {
+    ($value:literal $($unit:tt)+) => {
+        $crate::__quantity!(f64, $value, $($unit)+)
+    };
+}
+
+/// Creates an `f32` quantity from a number and a unit.
+///
+/// Exported as [`f32::q`](crate::f32::q).
+///
+/// # Examples
+///
+/// ```
+/// use danwi::f32::{q, types::Second};
+///
+/// let t: Second = q!(250 ms);
+/// assert_eq!(t, q!(0.25 s));
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! q_f32 {
+    ($value:literal $($unit:tt)+) => {
+        $crate::__quantity!(f32, $value, $($unit)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __quantity {
+    ($scalar:ty, $value:literal, $($unit:tt)+) => {
+        $crate::Quantity::<$scalar, _>::with_unit(($value) as $scalar, $crate::unit!($($unit)+))
+    };
+}
+
+/// Expands to the [`Dimension`](crate::dimension::Dimension) type of a product
+/// of powers of dimensions.
+///
+/// Factors are the base dimensions `T`, `L`, `M`, `I`, `K`, `N` and `J`,
+/// dimension types in scope (e.g. `Force`), `1` or parenthesized
+/// sub-expressions, combined with `*`, `/` and `^` with integer exponents.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{
+///     dim,
+///     dimension::{Force, Length, Power, ThermodynamicTemperature, Voltage},
+///     f64::{Quantity, QuantityExt},
+/// };
+///
+/// type Stress = dim!(Force / L^2);
+/// type ThermalConductivity = dim!(Power / (Length * ThermodynamicTemperature));
+/// type Transconductance = dim!(I / Voltage);
+///
+/// let f: Quantity<f64, dim!(M * L / T^2)> = 10.0.kg() * 2.0.mps2();
+/// assert_eq!(f, 20.0.N());
+///
+/// let stress: Quantity<f64, Stress> = f / (2.0.m() * 2.0.m());
+/// assert_eq!(stress, 5.0.Pa());
+///
+/// let v: Quantity<f64, dim!(L * T^-1)> = 3.0.mps();
+/// assert_eq!(v, 3.0.m() / 1.0.s());
+/// # let _: Option<(Quantity<f64, ThermalConductivity>, Quantity<f64, Transconductance>)> = None;
+/// ```
+#[macro_export]
+macro_rules! dim {
+    ($($dim:tt)+) => {
+        $crate::__dim!(@start $($dim)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dim {
+    (@start $a:tt ^ - $e:tt $($rest:tt)*) => {
+        $crate::__dim!(@fold [$crate::__dim!(@pow $a, - $e)] $($rest)*)
+    };
+    (@start $a:tt ^ $e:tt $($rest:tt)*) => {
+        $crate::__dim!(@fold [$crate::__dim!(@pow $a, $e)] $($rest)*)
+    };
+    (@start $a:tt $($rest:tt)*) => {
+        $crate::__dim!(@fold [$crate::__dim!(@atom $a)] $($rest)*)
+    };
+
+    (@fold [$acc:ty]) => {
+        $acc
+    };
+    (@fold [$acc:ty] $op:tt $a:tt ^ - $e:tt $($rest:tt)*) => {
+        $crate::__dim!(@fold [$crate::__dim!(@op $op $acc, $crate::__dim!(@pow $a, - $e))] $($rest)*)
+    };
+    (@fold [$acc:ty] $op:tt $a:tt ^ $e:tt $($rest:tt)*) => {
+        $crate::__dim!(@fold [$crate::__dim!(@op $op $acc, $crate::__dim!(@pow $a, $e))] $($rest)*)
+    };
+    (@fold [$acc:ty] $op:tt $a:tt $($rest:tt)*) => {
+        $crate::__dim!(@fold [$crate::__dim!(@op $op $acc, $crate::__dim!(@atom $a))] $($rest)*)
+    };
+
+    (@op * $lhs:ty, $rhs:ty) => {
+        $crate::dimension::DimensionMul<$lhs, $rhs>
+    };
+    (@op / $lhs:ty, $rhs:ty) => {
+        $crate::dimension::DimensionDiv<$lhs, $rhs>
+    };
+    (@pow $a:tt, $($e:tt)+) => {
+        $crate::dimension::DimensionPow<$crate::__dim!(@atom $a), $crate::__exponent!($($e)+)>
+    };
+
+    (@atom T) => { $crate::dimension::Time };
+    (@atom L) => { $crate::dimension::Length };
+    (@atom M) => { $crate::dimension::Mass };
+    (@atom I) => { $crate::dimension::ElectricCurrent };
+    (@atom K) => { $crate::dimension::ThermodynamicTemperature };
+    (@atom N) => { $crate::dimension::AmountOfSubstance };
+    (@atom J) => { $crate::dimension::LuminousIntensity };
+    (@atom 1) => { $crate::dimension::Dimensionless };
+    (@atom ($($inner:tt)+)) => { $crate::__dim!(@start $($inner)+) };
+    (@atom $dim:ident) => { $dim };
+}
+
+/// Declares additional base dimensions (see
+/// [`ExtraDimension`](crate::dimension::ExtraDimension)).
+///
+/// Each name becomes a public dimension type that can be combined with the SI
+/// dimensions and with each other, e.g. with [`dim!`], and used with
+/// [`define_units!`]. Each dimension is given the exponent position (`X2` to
+/// `X8`) that identifies it, after danwi's
+/// [`Information`](crate::dimension::Information) in `X1`. A position can only
+/// be declared once in a crate, which is checked when compiling it; crates that
+/// are used together must agree on the positions of their dimensions.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{
+///     Quantity, dim,
+///     dimension::{DataRate, Information},
+///     f64::QuantityExt,
+/// };
+///
+/// danwi::define_dimensions! {
+///     /// Amount of money.
+///     Currency = 2,
+///     /// Number of requests.
+///     Requests = 3,
+/// }
+///
+/// type Price = dim!(Currency / Information);
+///
+/// let volume = 1.0.GB();
+/// let rate: Quantity<f64, DataRate> = volume / 1000.0.s();
+/// let price: Quantity<f64, Price> = Quantity::<f64, Currency>::new(0.25) / volume;
+///
+/// let cost: Quantity<f64, Currency> = rate * 2000.0.s() * price;
+/// assert_eq!(cost.value(), 0.5);
+///
+/// let load = Quantity::<f64, Requests>::new(120.0) / 1.0.min();
+/// assert_eq!(load.dimension().to_string(), "T⁻¹·X3");
+/// ```
+#[macro_export]
+macro_rules! define_dimensions {
+    ($($(#[$attr:meta])* $name:ident = $position:tt),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            pub type $name = $crate::dimension::ExtraDimension<$crate::__define_dimensions!(@exponents $position)>;
+            $crate::__define_dimensions!(@claim $position);
+        )*
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_dimensions {
+    // a second declaration of a position defines the same macro twice
+    (@claim $position:tt) => {
+        $crate::__private::paste! {
+            #[doc(hidden)]
+            #[macro_export]
+            macro_rules! [<__danwi_dimension_x $position _is_declared>] {
+                () => {};
+            }
+        }
+    };
+
+    (@exponents 2) => { $crate::__define_dimensions!(@zeros 0) };
+    (@exponents 3) => { $crate::__define_dimensions!(@zeros 0 0) };
+    (@exponents 4) => { $crate::__define_dimensions!(@zeros 0 0 0) };
+    (@exponents 5) => { $crate::__define_dimensions!(@zeros 0 0 0 0) };
+    (@exponents 6) => { $crate::__define_dimensions!(@zeros 0 0 0 0 0) };
+    (@exponents 7) => { $crate::__define_dimensions!(@zeros 0 0 0 0 0 0) };
+    (@exponents 8) => { $crate::__define_dimensions!(@zeros 0 0 0 0 0 0 0) };
+    (@exponents $position:tt) => {
+        ::core::compile_error!(::core::concat!(
+            "additional dimensions are declared at positions 2 to 8, not ",
+            ::core::stringify!($position),
+        ))
+    };
+
+    (@zeros) => {
+        $crate::__private::typenum::TArr<$crate::__private::typenum::P1, $crate::__private::typenum::ATerm>
+    };
+    (@zeros 0 $($zero:tt)*) => {
+        $crate::__private::typenum::TArr<$crate::__private::typenum::Z0, $crate::__define_dimensions!(@zeros $($zero)*)>
+    };
+}
+
+/// Expands to the [`Unit`](crate::Unit) constant of a product of powers of
+/// units, with the prefixes combined.
+///
+/// Factors are the symbols of [`unit::constants`](crate::unit::constants), `1`
+/// or parenthesized sub-expressions, combined with `*`, `/` and `^` with
+/// integer exponents. Prefixes apply to the whole factor, so `cm^2` is 10⁻⁴ m².
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{dim, f64::QuantityExt, unit, Unit};
+///
+/// const KN_PER_CM2: Unit<dim!(M / (L * T^2))> = unit!(kN / cm^2);
+///
+/// let p = 2.0 * KN_PER_CM2;
+/// assert_eq!(p, 20.0.MPa());
+///
+/// let f = 3.0 * unit!(kg * m / s^2);
+/// assert_eq!(f, 3.0.N());
+///
+/// let rate = 1.0 * unit!(1 / ms);
+/// assert_eq!(rate, 1.0.kHz());
+///
+/// let a = 2.0 * unit!(m * s^-2);
+/// assert_eq!(a, 2.0.mps2());
+/// ```
+#[macro_export]
+macro_rules! unit {
+    ($($unit:tt)+) => {
+        $crate::__unit!(@start $($unit)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unit {
+    (@start $a:tt ^ - $e:tt $($rest:tt)*) => {
+        $crate::__unit!(@fold [$crate::__unit!(@pow $a, - $e)] $($rest)*)
+    };
+    (@start $a:tt ^ $e:tt $($rest:tt)*) => {
+        $crate::__unit!(@fold [$crate::__unit!(@pow $a, $e)] $($rest)*)
+    };
+    (@start $a:tt $($rest:tt)*) => {
+        $crate::__unit!(@fold [$crate::__unit!(@atom $a)] $($rest)*)
+    };
+
+    (@fold [$acc:expr]) => {
+        $acc
+    };
+    (@fold [$acc:expr] $op:tt $a:tt ^ - $e:tt $($rest:tt)*) => {
+        $crate::__unit!(@fold [$crate::__unit!(@op $op $acc, $crate::__unit!(@pow $a, - $e))] $($rest)*)
+    };
+    (@fold [$acc:expr] $op:tt $a:tt ^ $e:tt $($rest:tt)*) => {
+        $crate::__unit!(@fold [$crate::__unit!(@op $op $acc, $crate::__unit!(@pow $a, $e))] $($rest)*)
+    };
+    (@fold [$acc:expr] $op:tt $a:tt $($rest:tt)*) => {
+        $crate::__unit!(@fold [$crate::__unit!(@op $op $acc, $crate::__unit!(@atom $a))] $($rest)*)
+    };
+
+    (@op * $lhs:expr, $rhs:expr) => {
+        $lhs.times($rhs)
+    };
+    (@op / $lhs:expr, $rhs:expr) => {
+        $lhs.per($rhs)
+    };
+    (@pow $a:tt, $($e:tt)+) => {
+        $crate::__unit!(@atom $a).powi::<$crate::__exponent!($($e)+)>()
+    };
+
+    (@atom 1) => { $crate::Unit::<$crate::dimension::Dimensionless>::base() };
+    (@atom ($($inner:tt)+)) => { $crate::__unit!(@start $($inner)+) };
+    (@atom $unit:ident) => { $crate::unit::constants::$unit };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __exponent {
+    (0) => {
+        $crate::__private::typenum::Z0
+    };
+    (- $e:tt) => {
+        $crate::__private::paste! { $crate::__private::typenum::[<N $e>] }
+    };
+    ($e:tt) => {
+        $crate::__private::paste! { $crate::__private::typenum::[<P $e>] }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "f32")]
+macro_rules! __cfg_f32 {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "f32"))]
+macro_rules! __cfg_f32 {
+    ($($item:item)*) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "f64")]
+macro_rules! __cfg_f64 {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "f64"))]
+macro_rules! __cfg_f64 {
+    ($($item:item)*) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "ext-methods")]
+macro_rules! __cfg_ext_methods {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "ext-methods"))]
+macro_rules! __cfg_ext_methods {
+    ($($item:item)*) => {};
+}
@@ -4,8 +4,8 @@ use core::cmp::Ordering;
 
 impl<S: Scalar, D: Dimensions> PartialEq for Quantity<S, D> {
     fn eq(&self, other: &Self) -> bool {
-        let lhs_base = self.value.scale_by_power_of_10(self.unit.prefix);
-        let rhs_base = other.value.scale_by_power_of_10(other.unit.prefix);
+        let lhs_base = self.unit.to_base(self.value);
+        let rhs_base = other.unit.to_base(other.value);
         lhs_base == rhs_base
     }
 }
@@ -26,8 +26,8 @@ impl PartialEq<f64> for Quantity<f64, Dimensionless> {
 
 impl<S: Scalar> PartialOrd for Quantity<S, Dimensionless> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let lhs_base = self.value.scale_by_power_of_10(self.unit.prefix);
-        let rhs_base = other.value.scale_by_power_of_10(other.unit.prefix);
+        let lhs_base = self.unit.to_base(self.value);
+        let rhs_base = other.unit.to_base(other.value);
         lhs_base.partial_cmp(&rhs_base)
     }
 }
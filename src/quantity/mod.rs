@@ -1,4 +1,8 @@
-use crate::{dimension::Dimensions, scalar::Scalar, unit::Unit};
+use crate::{
+    dimension::{DimensionInfo, Dimensions},
+    scalar::Scalar,
+    unit::Unit,
+};
 
 mod cmp;
 mod convert;
@@ -35,10 +39,15 @@ where
         self.value
     }
 
+    /// Returns a runtime description of the dimension of the quantity.
+    #[inline]
+    pub const fn dimension(&self) -> DimensionInfo {
+        DimensionInfo::of::<D>()
+    }
+
     #[inline]
     pub fn to(&self, target_unit: Unit<D>) -> Self {
-        let prefix_diff = self.unit.prefix - target_unit.prefix;
-        let scaled_value = self.value.scale_by_power_of_10(prefix_diff);
-        Self::with_unit(scaled_value, target_unit)
+        let value = target_unit.convert(self.value, &self.unit);
+        Self::with_unit(value, target_unit)
     }
 }
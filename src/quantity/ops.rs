@@ -1,33 +1,75 @@
 use super::Quantity;
 use crate::{
-    dimension::{CanDivideBy, CanMultiplyWith, CanReciprocate, Dimensions},
+    dimension::{CanAdd, CanDivideBy, CanMultiplyWith, CanReciprocate, CanSubtract, Dimensions},
     scalar::Scalar,
 };
 use core::ops::{Add, Div, Mul, Neg, Sub};
 
 // Quantity + Quantity
-impl<S: Scalar, D: Dimensions> Add<Quantity<S, D>> for Quantity<S, D> {
-    type Output = Quantity<S, D>;
+impl<S, D1, D2> Add<Quantity<S, D2>> for Quantity<S, D1>
+where
+    S: Scalar,
+    D1: CanAdd<D2>,
+    D2: Dimensions,
+{
+    type Output = Quantity<S, D1>;
 
-    fn add(self, rhs: Quantity<S, D>) -> Self::Output {
-        let lhs_base = self.value.scale_by_power_of_10(self.unit.prefix);
-        let rhs_base = rhs.value.scale_by_power_of_10(rhs.unit.prefix);
+    fn add(self, rhs: Quantity<S, D2>) -> Self::Output {
+        let lhs_base = self.unit.to_base(self.value);
+        let rhs_base = rhs.unit.difference_to_base(rhs.value);
         Quantity::new(lhs_base + rhs_base)
     }
 }
 
 // Quantity - Quantity
-impl<S: Scalar, D: Dimensions> Sub<Quantity<S, D>> for Quantity<S, D> {
-    type Output = Quantity<S, D>;
+impl<S, D1, D2> Sub<Quantity<S, D2>> for Quantity<S, D1>
+where
+    S: Scalar,
+    D1: CanSubtract<D2>,
+    D2: Dimensions,
+{
+    type Output = Quantity<S, D1>;
 
-    fn sub(self, rhs: Quantity<S, D>) -> Self::Output {
-        let lhs_base = self.value.scale_by_power_of_10(self.unit.prefix);
-        let rhs_base = rhs.value.scale_by_power_of_10(rhs.unit.prefix);
+    fn sub(self, rhs: Quantity<S, D2>) -> Self::Output {
+        let lhs_base = self.unit.to_base(self.value);
+        let rhs_base = rhs.unit.difference_to_base(rhs.value);
         Quantity::new(lhs_base - rhs_base)
     }
 }
 
-// Quantity * Quantity
+/// Multiplication of two quantities, whose dimension is the product of theirs.
+///
+/// Dimensions are compared by their exponents, so the product has the type of
+/// every alias of that dimension.
+///
+/// # Examples
+///
+/// ```
+/// # use danwi::prelude::*;
+/// use danwi::dimension::{Area, Jerk, Momentum};
+///
+/// let p: Quantity<f64, Momentum> = 2.0.N() * 3.0.s();
+/// let impulse: NewtonSecond = p;
+/// assert_eq!(impulse, 6.0.kgmps());
+///
+/// let area: Quantity<f64, Area> = 2.0.m() * 3.0.m();
+/// assert_eq!(area, 6.0.m2());
+///
+/// let torque: NewtonMeter = 2.0.N() * 0.5.m();
+/// assert_eq!(torque.value(), 1.0);
+///
+/// let density: KilogramPerCubicMeter = 1.0.kg() / 1.0.L();
+/// assert_eq!(density, 1000.0.kgpm3());
+///
+/// let jerk: Quantity<f64, Jerk> = 1.0.mps2() / 2.0.s();
+/// assert_eq!(jerk, 0.5.mps3());
+///
+/// let rise: Kelvin = 0.5.KpW() * 40.0.W();
+/// assert_eq!(rise, 20.0.K());
+///
+/// let water: KilogramPerMole = 18.0.g() / 1.0.mol();
+/// assert_eq!(water * 2.0.mol(), 36.0.g());
+/// ```
 impl<S, D1, D2> Mul<Quantity<S, D2>> for Quantity<S, D1>
 where
     S: Scalar,
@@ -37,8 +79,8 @@ where
     type Output = Quantity<S, <D1 as CanMultiplyWith<D2>>::Output>;
 
     fn mul(self, rhs: Quantity<S, D2>) -> Self::Output {
-        let lhs_base = self.value.scale_by_power_of_10(self.unit.prefix);
-        let rhs_base = rhs.value.scale_by_power_of_10(rhs.unit.prefix);
+        let lhs_base = self.unit.difference_to_base(self.value);
+        let rhs_base = rhs.unit.difference_to_base(rhs.value);
         Quantity::new(lhs_base * rhs_base)
     }
 }
@@ -53,30 +95,18 @@ where
     type Output = Quantity<S, <D1 as CanDivideBy<D2>>::Output>;
 
     fn div(self, rhs: Quantity<S, D2>) -> Self::Output {
-        let lhs_base = self.value.scale_by_power_of_10(self.unit.prefix);
-        let rhs_base = rhs.value.scale_by_power_of_10(rhs.unit.prefix);
+        let lhs_base = self.unit.difference_to_base(self.value);
+        let rhs_base = rhs.unit.difference_to_base(rhs.value);
         Quantity::new(lhs_base / rhs_base)
     }
 }
 
-// Quantity * f32
-#[cfg(feature = "f32")]
-impl<D: Dimensions> Mul<f32> for Quantity<f32, D> {
-    type Output = Quantity<f32, D>;
-
-    #[inline]
-    fn mul(self, rhs: f32) -> Self::Output {
-        Self::with_unit(self.value * rhs, self.unit)
-    }
-}
-
-// Quantity * f64
-#[cfg(feature = "f64")]
-impl<D: Dimensions> Mul<f64> for Quantity<f64, D> {
-    type Output = Quantity<f64, D>;
+// Quantity * scalar
+impl<S: Scalar, D: Dimensions> Mul<S> for Quantity<S, D> {
+    type Output = Quantity<S, D>;
 
     #[inline]
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: S) -> Self::Output {
         Self::with_unit(self.value * rhs, self.unit)
     }
 }
@@ -103,24 +133,12 @@ impl<D: Dimensions> Mul<Quantity<f64, D>> for f64 {
     }
 }
 
-// Quantity / f32
-#[cfg(feature = "f32")]
-impl<D: Dimensions> Div<f32> for Quantity<f32, D> {
-    type Output = Quantity<f32, D>;
-
-    #[inline]
-    fn div(self, rhs: f32) -> Self::Output {
-        Self::with_unit(self.value / rhs, self.unit)
-    }
-}
-
-// Quantity / f64
-#[cfg(feature = "f64")]
-impl<D: Dimensions> Div<f64> for Quantity<f64, D> {
-    type Output = Quantity<f64, D>;
+// Quantity / scalar
+impl<S: Scalar, D: Dimensions> Div<S> for Quantity<S, D> {
+    type Output = Quantity<S, D>;
 
     #[inline]
-    fn div(self, rhs: f64) -> Self::Output {
+    fn div(self, rhs: S) -> Self::Output {
         Self::with_unit(self.value / rhs, self.unit)
     }
 }
@@ -135,7 +153,7 @@ where
 
     #[inline]
     fn div(self, rhs: Quantity<f32, D>) -> Self::Output {
-        let rhs_base = rhs.value.scale_by_power_of_10(rhs.unit.prefix);
+        let rhs_base = rhs.unit.difference_to_base(rhs.value);
         Quantity::new(self / rhs_base)
     }
 }
@@ -150,7 +168,7 @@ where
 
     #[inline]
     fn div(self, rhs: Quantity<f64, D>) -> Self::Output {
-        let rhs_base = rhs.value.scale_by_power_of_10(rhs.unit.prefix);
+        let rhs_base = rhs.unit.difference_to_base(rhs.value);
         Quantity::new(self / rhs_base)
     }
 }
@@ -161,6 +179,7 @@ where
 ///
 /// ```
 /// # use danwi::prelude::*;
+/// # use danwi::f64::constants::{N, kg};
 ///
 /// let force = 10.0_f64 * N;
 /// assert_eq!(force, 10.0.N());
@@ -180,3 +199,27 @@ where
         Self::with_unit(-self.value, self.unit)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "f64")]
+    #[test]
+    fn quantities_of_a_generic_dimension_add_and_subtract() {
+        use super::*;
+        use crate::unit::constants::{km, m, s};
+
+        fn sum<S: Scalar, D: Dimensions>(a: Quantity<S, D>, b: Quantity<S, D>) -> Quantity<S, D> {
+            a + b
+        }
+
+        fn difference<S: Scalar, D: Dimensions>(
+            a: Quantity<S, D>,
+            b: Quantity<S, D>,
+        ) -> Quantity<S, D> {
+            a - b
+        }
+
+        assert_eq!(sum(1.0 * km, 2.0 * m).value(), 1002.0);
+        assert_eq!(difference(3.0 * s, 1.0 * s).value(), 2.0);
+    }
+}
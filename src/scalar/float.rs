@@ -9,6 +9,10 @@ impl Scalar for f64 {
         let factor = libm::exp10(exponent as _);
         self * factor
     }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
 }
 
 impl Scalar for f32 {
@@ -20,4 +24,8 @@ impl Scalar for f32 {
         let factor = libm::exp10(exponent as _) as f32;
         self * factor
     }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
 }
@@ -1,5 +1,6 @@
 mod float;
 
+use crate::{dimension::Dimensions, quantity::Quantity, unit::Unit};
 use core::{
     fmt::{Debug, Display},
     ops::{Add, Div, Mul, Neg, Sub},
@@ -21,5 +22,52 @@ pub trait Scalar:
     + Sized
 {
     fn zero() -> Self;
+
+    /// Returns the multiplicative identity, `from_f64(1.0)` unless overridden.
+    fn one() -> Self {
+        Self::from_f64(1.0)
+    }
+
     fn scale_by_power_of_10(&self, exponent: i8) -> Self;
+    /// Converts a factor or offset of a unit (e.g. `60.0` for the minute).
+    fn from_f64(value: f64) -> Self;
+}
+
+/// Creates quantities from values of any [`Scalar`], including user-defined
+/// ones.
+///
+/// Unlike the generated `QuantityExt` methods, `of` takes the unit as a value,
+/// so it works with any unit constant, including compound units.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{ScalarExt, f64::constants::*};
+///
+/// let v = 3.0.of(kOhm) * 2.0.of(mA);
+/// assert_eq!(v, 6.0.of(V));
+///
+/// let speed = 1.5_f32.of(km / ms);
+/// assert_eq!(speed.value(), 1.5);
+/// ```
+pub trait ScalarExt: Scalar {
+    /// Returns a quantity of this value in `unit`.
+    #[inline]
+    fn of<D: Dimensions>(self, unit: Unit<D>) -> Quantity<Self, D> {
+        Quantity::with_unit(self, unit)
+    }
+}
+
+impl<S: Scalar> ScalarExt for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::Scalar;
+
+    #[test]
+    fn one_defaults_to_from_f64() {
+        assert_eq!(f64::one(), 1.0);
+        assert_eq!(f32::one(), 1.0);
+        assert_eq!(f32::from_f64(60.0), 60.0);
+    }
 }
@@ -1,507 +1,541 @@
+/// Defines units with their prefixed constants, quantity type aliases and
+/// extension methods.
+///
+/// Each unit is given as `name (symbol): Dimension`. Alternative names, such
+/// as plurals and other spellings, and alternative symbols follow the first
+/// one separated by `|`, e.g. `meter | meters | metre (m): Length`. The
+/// dimension can be followed by options in brackets, in this order:
+/// - `scale: -3`, the power of ten of the unit relative to the coherent unit of
+///   its dimension (e.g. for the gram),
+/// - `factor: 60.0`, a further factor that is not a power of ten (e.g. for the
+///   minute),
+/// - `offset: 273.15`, the value of the zero of the unit in coherent units
+///   (e.g. for the degree Celsius),
+/// - `prefixes: none`, the prefixes the unit accepts, `all`, `common` (G, M, k,
+///   m, μ, n and p), `none` or `default`, optionally followed by `+ binary` for
+///   the [binary prefixes](crate::unit::prefix::BINARY) (e.g. `prefixes:
+///   default + binary`). Units without it get the [default
+///   prefixes](crate::unit::prefix::DEFAULT) of the enabled features,
+/// - `kind: AbsorbedDose`, the kind of quantity the unit measures if its
+///   dimension is shared by several kinds (see
+///   [`UnitInfo::kind`](crate::unit::registry::UnitInfo::kind)),
+/// - `display: "m/s"`, the symbol shown in results if the symbol is only an
+///   identifier (see
+///   [`UnitInfo::display_symbol`](crate::unit::registry::UnitInfo::display_symbol)).
+///
+/// After a `;`, powers of these units whose prefixes are raised to the power
+/// as well can be defined as `name ([base] exponent): BaseDimension ^ P2` or
+/// `name (per_ [base]): BaseDimension ^ N1`, followed by the prefixes of the
+/// base unit as `[prefixes: none]` if it does not accept the default ones.
+///
+/// The macro generates, in the module it is invoked in:
+/// - `REGISTRY`, a [`Registry`](crate::unit::registry::Registry) of the units,
+/// - `constants`, with a [`Unit`](crate::Unit) constant for each prefix and
+///   unit symbol (e.g. `kB`, `attoB`, `μB`),
+/// - `types::f32` and `types::f64`, with a quantity alias for each unit,
+/// - `ext`, with the `QuantityExt` trait providing, for every
+///   [`Scalar`](crate::Scalar), a method for each prefixed symbol and each name
+///   (e.g. `2.0.kB()` and `2.0.bytes()`), also exported as `F32QuantityExt` and
+///   `F64QuantityExt`, with the `ext-methods` feature.
+///
+/// This is how danwi defines its own units from `units.txt`.
+///
+/// # Examples
+///
+/// ```
+/// mod imaging {
+///     use danwi::dimension::{Dimensionless, Time};
+///
+///     danwi::define_dimensions! {
+///         /// Number of pixels.
+///         Pixels = 2,
+///     }
+///
+///     /// Pixels per length.
+///     pub type PixelDensity = danwi::dim!(Pixels / L);
+///
+///     danwi::define_units! {
+///         pixel | pixels (px): Pixels [prefixes: default + binary],
+///         count (cnt): Dimensionless,
+///         frame | frames (fr): Time [factor: 0.04, prefixes: none],
+///         pixel_per_frame (pxpfr): danwi::dim!(Pixels / T) [display: "px/fr"],
+///         ;
+///         square_pixel ([px] 2): Pixels ^ P2 [prefixes: default + binary],
+///     }
+/// }
+///
+/// use danwi::f64::QuantityExt;
+/// use imaging::{constants::*, ext::F64QuantityExt as _};
+///
+/// let pitch: danwi::Quantity<f64, imaging::PixelDensity> = 1920.0.px() / 24.0.cm();
+/// assert_eq!(pitch * 1.0.mm(), 8.0.pixels());
+///
+/// assert_eq!(4.0 * kpx2, 4e6 * px2);
+/// assert_eq!(1.0.Mipx(), 1024.0.Kipx());
+/// assert_eq!(25.0.frames(), 1.0.s());
+/// assert_eq!(imaging::REGISTRY.by_symbol("px").unwrap().name(), "pixel");
+/// assert_eq!(imaging::REGISTRY.by_symbol("pxpfr").unwrap().display_symbol(), "px/fr");
+/// # let _ = 1.0.cnt();
+/// ```
+#[macro_export]
 macro_rules! define_units {
-    ($($name:ident ($symbol:ident): $dimension:ty),* $(,)?) => {
+    // methods of an extension trait, creating quantities of `$scalar`
+    (
+        @ext $scalar:ty;
+        $(
+            $name:ident [$($names:ident)*] ($symbol:ident [$($symbols:ident)*]): $dimension:ty
+            [$($prefixes:tt)*]
+        ),*;
+        $(
+            ([$($per:ident)?] $base:ident [$($suffix:literal)?]): $pdimension:ty
+            [$($pprefixes:tt)*]
+        ),*
+    ) => {
+        $(
+            $crate::__with_prefixes! {
+                [$($prefixes)*] __ext_methods {
+                    $scalar, $dimension; [$symbol $($symbols)*]; [$name $($names)*];
+                }
+            }
+        )*
+        $(
+            $crate::__with_prefixes! {
+                [$($pprefixes)*] __powered {
+                    __powered_ext_methods { $scalar, $pdimension; }
+                    [$($per)?] $base [$($suffix)?]
+                }
+            }
+        )*
+    };
+
+    (
+        $(
+            $name:ident $(| $names:ident)* ($symbol:ident $(| $symbols:ident)*): $dimension:ty
+            $([
+                $(scale: $scale:literal $(,)?)?
+                $(factor: $factor:literal $(,)?)?
+                $(offset: $offset:literal $(,)?)?
+                $(prefixes: $prefixes:ident $(+ $binary:ident)* $(,)?)?
+                $(kind: $kind:ident $(,)?)?
+                $(display: $display:literal $(,)?)?
+            ])?
+        ),* $(,)?
+        $(;
+            $(
+                $pname:ident ($($per:ident)? [$base:ident] $($suffix:literal)?): $base_dim:ident ^ $exp:ident
+                $([prefixes: $pprefixes:ident $(+ $pbinary:ident)*])?
+            ),* $(,)?
+        )?
+    ) => {
+        /// Registry of the units defined with `define_units!`.
+        pub const REGISTRY: $crate::unit::registry::Registry = $crate::__private::paste! {
+            $crate::unit::registry::Registry::new(&[
+                $(
+                    $crate::unit::registry::UnitInfo::new(
+                        stringify!($name),
+                        stringify!($symbol),
+                        $crate::dimension::DimensionInfo::of::<$dimension>(),
+                        $crate::__prefix_set!($($($prefixes $(+ $binary)*)?)?),
+                        constants::$symbol.prefix(),
+                        1,
+                    )
+                    .with_binary_prefixes($crate::__binary_prefix_set!($($($prefixes $(+ $binary)*)?)?))
+                    .with_names(&[$(stringify!($names)),*])
+                    .with_symbols(&[$(stringify!($symbols)),*])
+                    .with_factor(constants::$symbol.factor())
+                    .with_offset(constants::$symbol.offset())
+                    $($(.with_kind(stringify!($kind)))?)?
+                    $($(.with_display($display))?)?,
+                )*
+                $($(
+                    $crate::unit::registry::UnitInfo::new(
+                        stringify!($pname),
+                        concat!($(stringify!($per),)? stringify!($base), $(stringify!($suffix))?),
+                        $crate::dimension::DimensionInfo::of::<
+                            $crate::dimension::DimensionPow<$base_dim, $crate::__private::typenum::$exp>,
+                        >(),
+                        $crate::__prefix_set!($($pprefixes $(+ $pbinary)*)?),
+                        constants::[<$($per)? $base $($suffix)?>].prefix(),
+                        <$crate::__private::typenum::$exp as $crate::__private::typenum::Integer>::I8,
+                    )
+                    .with_binary_prefixes($crate::__binary_prefix_set!($($pprefixes $(+ $pbinary)*)?))
+                    .with_factor(constants::[<$($per)? $base $($suffix)?>].factor()),
+                )*)?
+            ])
+        };
+
         pub mod constants {
+            #![allow(non_upper_case_globals)]
+
             use super::*;
+            use $crate::Unit;
 
             $(
-                paste::paste! {
-                    // constants
-                    pub const [<Q $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::QUETTA);
-                    pub const [<R $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::RONNA);
-                    pub const [<Y $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::YOTTA);
-                    pub const [<Z $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::ZETTA);
-                    pub const [<E $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::EXA);
-                    pub const [<P $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::PETA);
-                    pub const [<T $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::TERA);
-                    pub const [<G $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::GIGA);
-                    pub const [<M $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::MEGA);
-                    pub const [<k $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::KILO);
-                    pub const [<h $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::HECTO);
-                    pub const [<da $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::DECA);
-                    pub const $symbol: Unit<$dimension> = Unit::base();
-                    pub const [<d $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::DECI);
-                    pub const [<c $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::CENTI);
-                    pub const [<m $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::MILLI);
-                    pub const [<u $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::MICRO);
-                    pub const [<n $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::NANO);
-                    pub const [<p $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::PICO);
-                    pub const [<f $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::FEMTO);
-                    // keyword collision for atto second (as)
-                    pub const [<atto $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::ATTO);
-                    pub const [<z $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::ZEPTO);
-                    pub const [<y $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::YOCTO);
-                    pub const [<r $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::RONTO);
-                    pub const [<q $symbol>]: Unit<$dimension> = Unit::with_prefix(prefix::QUECTO);
+                pub const $symbol: Unit<$dimension> = Unit::with_prefix(0 $($(+ $scale)?)?)
+                    $($(.scaled($factor as f64))?)?
+                    $($(.shifted($offset as f64))?)?
+                    $($(.with_kind(stringify!($kind)))?)?;
+                $(pub const $symbols: Unit<$dimension> = $symbol;)*
+
+                $crate::__with_prefixes! {
+                    [$($($prefixes $(+ $binary)*)?)?] __prefixed_constants {
+                        $dimension; $symbol; [$symbol $($symbols)*];
+                    }
                 }
             )*
+
+            // prefixed powers, e.g. `cm2` is (10⁻² m)²
+            $($(
+                $crate::__with_prefixes! {
+                    [$($pprefixes $(+ $pbinary)*)?] __powered {
+                        __powered_constants {
+                            $crate::dimension::DimensionPow<$base_dim, $crate::__private::typenum::$exp>; $exp;
+                        }
+                        [$($per)?] $base [$($suffix)?]
+                    }
+                }
+            )*)?
         }
 
         pub mod types {
             pub use super::*;
 
-            #[cfg(feature = "f32")]
-            pub mod f32 {
-                pub use super::*;
-                pub use crate::Quantity;
+            $crate::__cfg_f32! {
+                pub mod f32 {
+                    pub use super::*;
+                    pub use $crate::Quantity;
 
-                $(
-                    paste::paste! {
-                        pub type [<$name:camel>] = Quantity<f32, $dimension>;
-                    }
-                )*
+                    $(
+                        $crate::__private::paste! {
+                            pub type [<$name:camel>] = Quantity<f32, $dimension>;
+                        }
+                    )*
+                    $($(
+                        $crate::__private::paste! {
+                            pub type [<$pname:camel>] = Quantity<f32, $crate::dimension::DimensionPow<$base_dim, $crate::__private::typenum::$exp>>;
+                        }
+                    )*)?
+                }
             }
 
-            #[cfg(feature = "f64")]
-            pub mod f64 {
-                pub use super::*;
-                pub use crate::Quantity;
+            $crate::__cfg_f64! {
+                pub mod f64 {
+                    pub use super::*;
+                    pub use $crate::Quantity;
 
-                $(
-                    paste::paste! {
-                        pub type [<$name:camel>] = Quantity<f64, $dimension>;
-                    }
-                )*
+                    $(
+                        $crate::__private::paste! {
+                            pub type [<$name:camel>] = Quantity<f64, $dimension>;
+                        }
+                    )*
+                    $($(
+                        $crate::__private::paste! {
+                            pub type [<$pname:camel>] = Quantity<f64, $crate::dimension::DimensionPow<$base_dim, $crate::__private::typenum::$exp>>;
+                        }
+                    )*)?
+                }
             }
         }
 
         pub mod ext {
             #![allow(non_snake_case)]
 
-            use super::{*, constants::*};
+            $crate::__cfg_ext_methods! {
+                use super::{*, constants::*};
 
-            paste::paste! {
-                #[cfg(feature = "f32")]
-                pub trait F32QuantityExt {
-                    $(
-                        fn [<Q $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<R $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<Y $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<Z $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<E $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<P $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<T $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<G $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<M $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<k $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<h $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<da $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn $symbol(self) -> Quantity<f32, $dimension>;
-                        fn [<d $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<c $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<m $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<u $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<n $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<p $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<f $symbol>](self) -> Quantity<f32, $dimension>;
-                        // keyword collision for atto second (as)
-                        fn [<atto $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<z $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<y $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<r $symbol>](self) -> Quantity<f32, $dimension>;
-                        fn [<q $symbol>](self) -> Quantity<f32, $dimension>;
-
-                        fn [<quetta $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<ronna $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<yotta $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<zetta $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<exa $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<peta $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<tera $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<giga $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<mega $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<kilo $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<hecto $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<deca $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<$name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<deci $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<centi $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<milli $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<micro $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<nano $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<pico $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<femto $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<atto $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<zepto $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<yocto $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<ronto $name:lower>](self) -> Quantity<f32, $dimension>;
-                        fn [<quecto $name:lower>](self) -> Quantity<f32, $dimension>;
-                    )*
+                /// Methods creating a quantity in each prefixed unit (e.g.
+                /// `2.0.kV()`), for every `Scalar`.
+                ///
+                /// They are shorthands for `ScalarExt::of` (e.g. `2.0.of(kV)`).
+                /// The scalar type of a literal such as `2.0` is inferred from
+                /// its use, and is `f64` if nothing else fixes it.
+                pub trait QuantityExt: $crate::Scalar {
+                    $crate::define_units!(@ext Self; $(
+                        $name [$($names)*] ($symbol [$($symbols)*]): $dimension
+                        [$($($prefixes $(+ $binary)*)?)?]
+                    ),*; $($(
+                        ([$($per)?] $base [$($suffix)?]): $crate::dimension::DimensionPow<$base_dim, $crate::__private::typenum::$exp>
+                        [$($pprefixes $(+ $pbinary)*)?]
+                    ),*)?);
                 }
 
-                #[cfg(feature = "f64")]
-                pub trait F64QuantityExt {
-                    $(
-                        fn [<Q $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<R $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<Y $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<Z $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<E $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<P $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<T $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<G $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<M $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<k $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<h $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<da $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn $symbol(self) -> Quantity<f64, $dimension>;
-                        fn [<d $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<c $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<m $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<u $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<n $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<p $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<f $symbol>](self) -> Quantity<f64, $dimension>;
-                        // keyword collision for atto second (as)
-                        fn [<atto $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<z $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<y $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<r $symbol>](self) -> Quantity<f64, $dimension>;
-                        fn [<q $symbol>](self) -> Quantity<f64, $dimension>;
-
-                        fn [<quetta $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<ronna $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<yotta $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<zetta $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<exa $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<peta $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<tera $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<giga $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<mega $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<kilo $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<hecto $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<deca $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<$name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<deci $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<centi $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<milli $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<micro $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<nano $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<pico $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<femto $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<atto $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<zepto $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<yocto $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<ronto $name:lower>](self) -> Quantity<f64, $dimension>;
-                        fn [<quecto $name:lower>](self) -> Quantity<f64, $dimension>;
-                    )*
+                impl<S: $crate::Scalar> QuantityExt for S {}
+
+                $crate::__cfg_f32! {
+                    #[doc(no_inline)]
+                    pub use QuantityExt as F32QuantityExt;
+                }
+
+                $crate::__cfg_f64! {
+                    #[doc(no_inline)]
+                    pub use QuantityExt as F64QuantityExt;
                 }
             }
+        }
+    };
 
-            paste::paste! {
-                #[cfg(feature = "f32")]
-                impl F32QuantityExt for f32 {
-                    $(
-                        fn [<Q $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<Q $symbol>]
-                        }
-                        fn [<R $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<R $symbol>]
-                        }
-                        fn [<Y $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<Y $symbol>]
-                        }
-                        fn [<Z $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<Z $symbol>]
-                        }
-                        fn [<E $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<E $symbol>]
-                        }
-                        fn [<P $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<P $symbol>]
-                        }
-                        fn [<T $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<T $symbol>]
-                        }
-                        fn [<G $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<G $symbol>]
-                        }
-                        fn [<M $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<M $symbol>]
-                        }
-                        fn [<k $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<k $symbol>]
-                        }
-                        fn [<h $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<h $symbol>]
-                        }
-                        fn [<da $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<da $symbol>]
-                        }
-                        fn $symbol(self) -> Quantity<f32, $dimension> {
-                            self * $symbol
-                        }
-                        fn [<d $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<d $symbol>]
-                        }
-                        fn [<c $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<c $symbol>]
-                        }
-                        fn [<m $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<m $symbol>]
-                        }
-                        fn [<u $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<u $symbol>]
-                        }
-                        fn [<n $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<n $symbol>]
-                        }
-                        fn [<p $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<p $symbol>]
-                        }
-                        fn [<f $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<f $symbol>]
-                        }
-                        fn [<atto $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<atto $symbol>]
-                        }
-                        fn [<z $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<z $symbol>]
-                        }
-                        fn [<y $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<y $symbol>]
-                        }
-                        fn [<r $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<r $symbol>]
-                        }
-                        fn [<q $symbol>](self) -> Quantity<f32, $dimension> {
-                            self * [<q $symbol>]
-                        }
+}
 
-                        fn [<quetta $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<Q $symbol>]()
-                        }
-                        fn [<ronna $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<R $symbol>]()
-                        }
-                        fn [<yotta $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<Y $symbol>]()
-                        }
-                        fn [<zetta $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<Z $symbol>]()
-                        }
-                        fn [<exa $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<E $symbol>]()
-                        }
-                        fn [<peta $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<P $symbol>]()
-                        }
-                        fn [<tera $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<T $symbol>]()
-                        }
-                        fn [<giga $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<G $symbol>]()
-                        }
-                        fn [<mega $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<M $symbol>]()
-                        }
-                        fn [<kilo $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<k $symbol>]()
-                        }
-                        fn [<hecto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<h $symbol>]()
-                        }
-                        fn [<deca $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<da $symbol>]()
-                        }
-                        fn [<$name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.$symbol()
-                        }
-                        fn [<deci $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<d $symbol>]()
-                        }
-                        fn [<centi $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<c $symbol>]()
-                        }
-                        fn [<milli $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<m $symbol>]()
-                        }
-                        fn [<micro $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<u $symbol>]()
-                        }
-                        fn [<nano $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<n $symbol>]()
-                        }
-                        fn [<pico $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<p $symbol>]()
-                        }
-                        fn [<femto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<f $symbol>]()
-                        }
-                        fn [<atto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<atto $symbol>]()
-                        }
-                        fn [<zepto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<z $symbol>]()
-                        }
-                        fn [<yocto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<y $symbol>]()
-                        }
-                        fn [<ronto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<r $symbol>]()
-                        }
-                        fn [<quecto $name:lower>](self) -> Quantity<f32, $dimension> {
-                            self.[<q $symbol>]()
-                        }
-                    )*
-                }
+/// Returns the decimal prefixes of a prefix policy (the
+/// [default](crate::unit::prefix::DEFAULT) if omitted) as a slice of
+/// [`Prefix`](crate::unit::prefix::Prefix).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __prefix_set {
+    () => {
+        $crate::unit::prefix::DEFAULT
+    };
+    (default $(+ $binary:ident)*) => {
+        $crate::unit::prefix::DEFAULT
+    };
+    (all $(+ $binary:ident)*) => {
+        $crate::unit::prefix::ALL
+    };
+    (common $(+ $binary:ident)*) => {
+        $crate::unit::prefix::COMMON
+    };
+    (none $(+ $binary:ident)*) => {
+        $crate::unit::prefix::NONE
+    };
+    (binary $(+ $binary:ident)*) => {
+        $crate::unit::prefix::NONE
+    };
+}
 
-                #[cfg(feature = "f64")]
-                impl F64QuantityExt for f64 {
-                    $(
-                        fn [<Q $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<Q $symbol>]
-                        }
-                        fn [<R $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<R $symbol>]
-                        }
-                        fn [<Y $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<Y $symbol>]
-                        }
-                        fn [<Z $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<Z $symbol>]
-                        }
-                        fn [<E $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<E $symbol>]
-                        }
-                        fn [<P $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<P $symbol>]
-                        }
-                        fn [<T $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<T $symbol>]
-                        }
-                        fn [<G $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<G $symbol>]
-                        }
-                        fn [<M $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<M $symbol>]
-                        }
-                        fn [<k $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<k $symbol>]
-                        }
-                        fn [<h $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<h $symbol>]
-                        }
-                        fn [<da $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<da $symbol>]
-                        }
-                        fn $symbol(self) -> Quantity<f64, $dimension> {
-                            self * $symbol
-                        }
-                        fn [<d $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<d $symbol>]
-                        }
-                        fn [<c $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<c $symbol>]
-                        }
-                        fn [<m $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<m $symbol>]
-                        }
-                        fn [<u $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<u $symbol>]
-                        }
-                        fn [<n $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<n $symbol>]
-                        }
-                        fn [<p $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<p $symbol>]
-                        }
-                        fn [<f $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<f $symbol>]
-                        }
-                        fn [<atto $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<atto $symbol>]
-                        }
-                        fn [<z $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<z $symbol>]
-                        }
-                        fn [<y $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<y $symbol>]
-                        }
-                        fn [<r $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<r $symbol>]
-                        }
-                        fn [<q $symbol>](self) -> Quantity<f64, $dimension> {
-                            self * [<q $symbol>]
-                        }
+/// Returns the binary prefixes of a prefix policy as a slice of
+/// [`Prefix`](crate::unit::prefix::Prefix).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __binary_prefix_set {
+    (binary $(+ $policy:ident)*) => {
+        $crate::unit::prefix::BINARY
+    };
+    ($policy:ident + binary $(+ $rest:ident)*) => {
+        $crate::unit::prefix::BINARY
+    };
+    ($($policy:tt)*) => {
+        $crate::unit::prefix::NONE
+    };
+}
 
-                        fn [<quetta $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<Q $symbol>]()
-                        }
-                        fn [<ronna $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<R $symbol>]()
-                        }
-                        fn [<yotta $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<Y $symbol>]()
-                        }
-                        fn [<zetta $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<Z $symbol>]()
-                        }
-                        fn [<exa $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<E $symbol>]()
-                        }
-                        fn [<peta $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<P $symbol>]()
-                        }
-                        fn [<tera $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<T $symbol>]()
-                        }
-                        fn [<giga $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<G $symbol>]()
-                        }
-                        fn [<mega $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<M $symbol>]()
-                        }
-                        fn [<kilo $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<k $symbol>]()
-                        }
-                        fn [<hecto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<h $symbol>]()
-                        }
-                        fn [<deca $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<da $symbol>]()
-                        }
-                        fn [<$name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.$symbol()
-                        }
-                        fn [<deci $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<d $symbol>]()
-                        }
-                        fn [<centi $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<c $symbol>]()
-                        }
-                        fn [<milli $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<m $symbol>]()
-                        }
-                        fn [<micro $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<u $symbol>]()
-                        }
-                        fn [<nano $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<n $symbol>]()
-                        }
-                        fn [<pico $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<p $symbol>]()
-                        }
-                        fn [<femto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<f $symbol>]()
-                        }
-                        fn [<atto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<atto $symbol>]()
-                        }
-                        fn [<zepto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<z $symbol>]()
-                        }
-                        fn [<yocto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<y $symbol>]()
-                        }
-                        fn [<ronto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<r $symbol>]()
-                        }
-                        fn [<quecto $name:lower>](self) -> Quantity<f64, $dimension> {
-                            self.[<q $symbol>]()
-                        }
-                    )*
-                }
-            }
+/// Invokes `$crate::$callback!` with the given arguments followed by the
+/// prefixes of a prefix policy (the default if omitted), as a list of
+/// `(symbol method value)` triples, where `method` is the `Unit` method that
+/// applies the prefix `value`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_prefixes {
+    ([] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! { @add [] [default] $callback $args }
+    };
+    ([$policy:ident $(+ $more:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! { @add [] [$policy $($more)*] $callback $args }
+    };
+
+    // adds the prefixes of each policy to the lists
+    (@add $symbols:tt [] $callback:ident { $($args:tt)* }) => {
+        $crate::$callback! { $($args)* $symbols }
+    };
+    (@add $symbols:tt [default $($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_default_prefixes! { $symbols [$($rest)*] $callback $args }
+    };
+    (@add [$($symbols:tt)*] [all $($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! {
+            @add
+            [
+                $($symbols)*
+                (Q prefixed QUETTA) (R prefixed RONNA) (Y prefixed YOTTA) (Z prefixed ZETTA)
+                (E prefixed EXA) (P prefixed PETA) (T prefixed TERA) (G prefixed GIGA)
+                (M prefixed MEGA) (k prefixed KILO) (h prefixed HECTO) (da prefixed DECA)
+                (d prefixed DECI) (c prefixed CENTI) (m prefixed MILLI) (u prefixed MICRO)
+                (μ prefixed MICRO) (n prefixed NANO) (p prefixed PICO) (f prefixed FEMTO)
+                // keyword collision for atto second (as)
+                (atto prefixed ATTO)
+                (z prefixed ZEPTO) (y prefixed YOCTO) (r prefixed RONTO) (q prefixed QUECTO)
+            ]
+            [$($rest)*] $callback $args
         }
     };
+    (@add [$($symbols:tt)*] [common $($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! {
+            @add
+            [
+                $($symbols)*
+                (G prefixed GIGA) (M prefixed MEGA) (k prefixed KILO) (m prefixed MILLI)
+                (u prefixed MICRO) (μ prefixed MICRO) (n prefixed NANO) (p prefixed PICO)
+            ]
+            [$($rest)*] $callback $args
+        }
+    };
+    (@add $symbols:tt [none $($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! { @add $symbols [$($rest)*] $callback $args }
+    };
+    (@add [$($symbols:tt)*] [binary $($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! {
+            @add
+            [
+                $($symbols)*
+                (Ei scaled EXBI) (Pi scaled PEBI) (Ti scaled TEBI) (Gi scaled GIBI)
+                (Mi scaled MEBI) (Ki scaled KIBI)
+            ]
+            [$($rest)*] $callback $args
+        }
+    };
+}
+
+/// Adds the prefixes selected by the `prefixes-all` and `prefixes-common`
+/// features in `__with_prefixes!`.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "prefixes-all")]
+macro_rules! __with_default_prefixes {
+    ($symbols:tt [$($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! { @add $symbols [all $($rest)*] $callback $args }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "prefixes-common", not(feature = "prefixes-all")))]
+macro_rules! __with_default_prefixes {
+    ($symbols:tt [$($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! { @add $symbols [common $($rest)*] $callback $args }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(any(feature = "prefixes-common", feature = "prefixes-all")))]
+macro_rules! __with_default_prefixes {
+    ($symbols:tt [$($rest:ident)*] $callback:ident $args:tt) => {
+        $crate::__with_prefixes! { @add $symbols [none $($rest)*] $callback $args }
+    };
+}
+
+/// Defines the prefixed constants of each of the symbols of a unit.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __prefixed_constants {
+    ($dimension:ty; $unit:ident; []; $prefixes:tt) => {};
+    (
+        $dimension:ty; $unit:ident; [$symbol:ident $($symbols:ident)*];
+        [$(($prefix:ident $apply:ident $value:ident))*]
+    ) => {
+        $crate::__private::paste! {
+            $(
+                pub const [<$prefix $symbol>]: $crate::Unit<$dimension> =
+                    $unit.$apply($crate::unit::prefix::$value);
+            )*
+        }
+
+        $crate::__prefixed_constants! {
+            $dimension; $unit; [$($symbols)*]; [$(($prefix $apply $value))*]
+        }
+    };
+}
+
+/// Defines the extension methods of each of the prefixed symbols of a unit,
+/// whose first symbol is its constant, and of each of its names.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ext_methods {
+    ($scalar:ty, $dimension:ty; []; [$($name:ident)*]; $unit:ident; $prefixes:tt) => {
+        $crate::__private::paste! {
+            $(
+                $crate::__ext_method! { $scalar, $dimension, [<$name:lower>], $unit }
+            )*
+        }
+    };
+    (
+        $scalar:ty, $dimension:ty; [$symbol:ident $($symbols:ident)*]; $names:tt;
+        [$(($prefix:ident $apply:ident $value:ident))*]
+    ) => {
+        $crate::__ext_methods! {
+            $scalar, $dimension; [$symbol $($symbols)*]; $names; $symbol;
+            [$(($prefix $apply $value))*]
+        }
+    };
+    (
+        $scalar:ty, $dimension:ty; [$symbol:ident $($symbols:ident)*]; $names:tt; $unit:ident;
+        [$(($prefix:ident $apply:ident $value:ident))*]
+    ) => {
+        $crate::__private::paste! {
+            $crate::__ext_method! { $scalar, $dimension, $symbol, $symbol }
+            $(
+                $crate::__ext_method! { $scalar, $dimension, [<$prefix $symbol>], [<$prefix $symbol>] }
+            )*
+        }
+
+        $crate::__ext_methods! {
+            $scalar, $dimension; [$($symbols)*]; $names; $unit;
+            [$(($prefix $apply $value))*]
+        }
+    };
+}
+
+/// Defines an extension method returning a quantity of `$scalar` in `$unit`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ext_method {
+    ($scalar:ty, $dimension:ty, $method:ident, $unit:ident) => {
+        #[inline]
+        fn $method(self) -> $crate::Quantity<$scalar, $dimension> {
+            $crate::Quantity::with_unit(self.into(), $unit)
+        }
+    };
+}
+
+/// Invokes `$crate::$callback!` with the given arguments followed by the
+/// names of the power of a unit and its prefixed forms (e.g. `m2`, `cm2` or
+/// `per_ms`), each with the name of the corresponding base unit.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __powered {
+    (
+        $callback:ident { $($args:tt)* } [] $base:ident [$suffix:literal]
+        [$(($prefix:ident $apply:ident $value:ident))*]
+    ) => {
+        $crate::__private::paste! {
+            $crate::$callback! { $($args)* [([<$base $suffix>] $base) $(([<$prefix $base $suffix>] [<$prefix $base>]))*] }
+        }
+    };
+    (
+        $callback:ident { $($args:tt)* } [per_] $base:ident []
+        [$(($prefix:ident $apply:ident $value:ident))*]
+    ) => {
+        $crate::__private::paste! {
+            $crate::$callback! { $($args)* [([<per_ $base>] $base) $(([<per_ $prefix $base>] [<$prefix $base>]))*] }
+        }
+    };
+    (
+        $callback:ident { $($args:tt)* } [per_] $base:ident [$suffix:literal]
+        [$(($prefix:ident $apply:ident $value:ident))*]
+    ) => {
+        $crate::__private::paste! {
+            $crate::$callback! { $($args)* [([<per_ $base $suffix>] $base) $(([<per_ $prefix $base $suffix>] [<$prefix $base>]))*] }
+        }
+    };
+}
+
+/// Defines the constants of a power of a unit.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __powered_constants {
+    ($dimension:ty; $exp:ident; [$(($name:ident $base:ident))*]) => {
+        $(
+            pub const $name: $crate::Unit<$dimension> =
+                $base.powi::<$crate::__private::typenum::$exp>();
+        )*
+    };
+}
+
+/// Defines the extension methods of a power of a unit.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __powered_ext_methods {
+    ($scalar:ty, $dimension:ty; [$(($name:ident $base:ident))*]) => {
+        $(
+            $crate::__ext_method! { $scalar, $dimension, $name, $name }
+        )*
+    };
 }
@@ -1,16 +1,21 @@
 #![allow(non_upper_case_globals)]
 
-use crate::{dimension::*, quantity::Quantity};
+use crate::{dimension::*, scalar::Scalar};
 use core::marker::PhantomData;
+use typenum::Integer;
 
 #[macro_use]
 mod macros;
 mod ops;
-mod prefix;
+pub mod prefix;
+pub mod registry;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Unit<D: Dimensions> {
     pub(crate) prefix: i8,
+    pub(crate) factor: f64,
+    pub(crate) offset: f64,
+    pub(crate) kind: Option<&'static str>,
     _phantom: PhantomData<D>,
 }
 
@@ -18,6 +23,9 @@ impl<D: Dimensions> Unit<D> {
     pub const fn with_prefix(prefix: i8) -> Self {
         Self {
             prefix,
+            factor: 1.0,
+            offset: 0.0,
+            kind: None,
             _phantom: PhantomData,
         }
     }
@@ -25,6 +33,277 @@ impl<D: Dimensions> Unit<D> {
     pub const fn base() -> Self {
         Self::with_prefix(0)
     }
+
+    /// Returns the power of ten of the unit relative to the coherent unit of
+    /// its dimension (e.g. `3` for `km` and `-3` for `g`).
+    #[inline]
+    pub const fn prefix(&self) -> i8 {
+        self.prefix
+    }
+
+    /// Returns the unit with a prefix of `10^prefix` applied (e.g.
+    /// `m.prefixed(prefix::KILO)` is `km`).
+    pub const fn prefixed(self, prefix: i8) -> Self {
+        Self {
+            prefix: self.prefix + prefix,
+            ..self
+        }
+    }
+
+    /// Returns the unit multiplied by a factor that is not a power of ten
+    /// (e.g. `60.0` for the minute).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use danwi::prelude::*;
+    /// # use danwi::f64::constants::{min, s};
+    ///
+    /// assert_eq!(s.scaled(60.0).factor(), min.factor());
+    /// assert_eq!(90.0.s().to(min).value(), 1.5);
+    /// assert_eq!(2.0.hours(), 120.0.minutes());
+    /// ```
+    pub const fn scaled(self, factor: f64) -> Self {
+        Self {
+            factor: self.factor * factor,
+            ..self
+        }
+    }
+
+    /// Returns the unit with its zero shifted by `offset` coherent units (e.g.
+    /// `273.15` for the degree Celsius).
+    ///
+    /// A quantity in a shifted unit is an absolute value (e.g. a temperature
+    /// of 20 °C is 293.15 K) where it is converted, compared, or on the left of
+    /// an addition or subtraction. Everywhere else, i.e. on the right of an
+    /// addition or subtraction and as a factor of a product or quotient with
+    /// another quantity, it is a difference whose offset is dropped. So 20 °C
+    /// + 5 K and 20 °C + 5 °C are both 298.15 K, and 20 °C · 1 m is 20 K·m.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use danwi::prelude::*;
+    /// # use danwi::f64::constants::{K};
+    /// use danwi::dimension::ThermodynamicTemperature;
+    ///
+    /// const degC: Unit<ThermodynamicTemperature> = K.shifted(273.15);
+    ///
+    /// assert_eq!((20.0_f64 * degC).to(K).value(), 293.15);
+    /// assert_eq!(293.15.K().to(degC).value(), 20.0);
+    /// assert_eq!(20.0_f64 * degC + 5.0.K(), 298.15.K());
+    /// assert_eq!(20.0_f64 * degC + 5.0_f64 * degC, 298.15.K());
+    /// assert_eq!((20.0_f64 * degC * 1.0.m()).value(), 20.0);
+    /// ```
+    pub const fn shifted(self, offset: f64) -> Self {
+        Self {
+            offset: self.offset + offset,
+            ..self
+        }
+    }
+
+    /// Returns the unit as one of a kind of quantity sharing its dimension
+    /// with others (e.g. `AbsorbedDose` for the gray).
+    ///
+    /// Kinds have the type of their dimension, so quantities of different
+    /// kinds (e.g. `1.0.Gy() + 1.0.Sv()`) are only told apart once they are
+    /// converted into a [`DynQuantity`](crate::DynQuantity), which carries the
+    /// kind of their unit (see
+    /// [`UnitInfo::kind`](crate::unit::registry::UnitInfo::kind)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use danwi::prelude::*;
+    /// # use danwi::f64::constants::{Gy, J, kg, mGy};
+    /// use danwi::DynQuantity;
+    ///
+    /// assert_eq!(Gy.kind(), Some("AbsorbedDose"));
+    /// assert_eq!(mGy.kind(), Some("AbsorbedDose"));
+    /// assert_eq!((J / kg).kind(), None);
+    ///
+    /// let dose = DynQuantity::from(2.0.Gy());
+    /// assert_eq!(dose.kind(), Some("AbsorbedDose"));
+    /// assert!(dose.checked_add(DynQuantity::from(1.0.Sv())).is_err());
+    /// assert!(dose.checked_add(DynQuantity::from(1.0.J() / 1.0.kg())).is_ok());
+    /// ```
+    pub const fn with_kind(self, kind: &'static str) -> Self {
+        Self {
+            kind: Some(kind),
+            ..self
+        }
+    }
+
+    /// Returns the kind of quantity the unit measures, if it was given one
+    /// with [`with_kind`](Self::with_kind).
+    #[inline]
+    pub const fn kind(&self) -> Option<&'static str> {
+        self.kind
+    }
+
+    /// Returns the factor of the unit besides its power of ten (e.g. `60.0`
+    /// for the minute and `1.0` for `km`).
+    #[inline]
+    pub const fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Returns the value of the zero of the unit in coherent units (e.g.
+    /// `273.15` for the degree Celsius).
+    #[inline]
+    pub const fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Converts a value in this unit into the coherent unit of its dimension.
+    #[inline]
+    pub(crate) fn to_base<S: Scalar>(&self, value: S) -> S {
+        value.scale_by_power_of_10(self.prefix) * S::from_f64(self.factor)
+            + S::from_f64(self.offset)
+    }
+
+    /// Converts a difference of values in this unit into the coherent unit of
+    /// its dimension, dropping the offset.
+    #[inline]
+    pub(crate) fn difference_to_base<S: Scalar>(&self, value: S) -> S {
+        value.scale_by_power_of_10(self.prefix) * S::from_f64(self.factor)
+    }
+
+    /// Converts a value from the `from` unit into this unit, cancelling out
+    /// the prefixes before scaling.
+    #[inline]
+    pub(crate) fn convert<S: Scalar>(&self, value: S, from: &Self) -> S {
+        let offset = S::from_f64((from.offset - self.offset) / self.factor);
+        value.scale_by_power_of_10(from.prefix - self.prefix)
+            * S::from_f64(from.factor / self.factor)
+            + offset.scale_by_power_of_10(-self.prefix)
+    }
+
+    /// Returns the product of two units (e.g. `N·m`).
+    ///
+    /// The offsets of the units are dropped, so that e.g. a temperature
+    /// difference in degrees Celsius can be multiplied like one in kelvins.
+    pub const fn times<D2>(self, rhs: Unit<D2>) -> Unit<<D as CanMultiplyWith<D2>>::Output>
+    where
+        D: CanMultiplyWith<D2>,
+        D2: Dimensions,
+    {
+        Unit::with_prefix(self.prefix + rhs.prefix).scaled(self.factor * rhs.factor)
+    }
+
+    /// Returns the quotient of two units (e.g. `m/s`), dropping their offsets.
+    pub const fn per<D2>(self, rhs: Unit<D2>) -> Unit<<D as CanDivideBy<D2>>::Output>
+    where
+        D: CanDivideBy<D2>,
+        D2: Dimensions,
+    {
+        Unit::with_prefix(self.prefix - rhs.prefix).scaled(self.factor / rhs.factor)
+    }
+
+    /// Returns the unit raised to the integer power `E` (e.g. `cm²`), applying
+    /// the prefix to each factor.
+    ///
+    /// The powers defined in `units.txt` are available as constants such as
+    /// `cm2`, `mm3` and `per_ms`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use danwi::prelude::*;
+    /// # use danwi::f64::constants::{cm, cm2, km2, m2, m3, mm, mm2, mm3, per_ms};
+    /// use typenum::P2;
+    ///
+    /// assert_eq!(1.0 * cm.powi::<P2>(), 1.0 * cm2);
+    /// assert_eq!(2.0 * mm * (4.0 * mm), 8.0 * mm2);
+    /// assert_eq!(3.0 * km2, 3e6 * m2);
+    /// assert_eq!((1.0_f64 * m3).to(mm3).value(), 1e9);
+    /// assert_eq!(5.0 * per_ms, 5.0.kHz());
+    /// ```
+    pub const fn powi<E>(self) -> Unit<<D as CanRaiseTo<E>>::Output>
+    where
+        D: CanRaiseTo<E>,
+        E: Integer,
+    {
+        let mut factor = 1.0;
+        let mut i = 0;
+        while i < E::I8.unsigned_abs() {
+            factor *= self.factor;
+            i += 1;
+        }
+
+        if E::I8 < 0 {
+            factor = 1.0 / factor;
+        }
+
+        Unit::with_prefix(self.prefix * E::I8).scaled(factor)
+    }
 }
 
 include!(concat!(env!("OUT_DIR"), "/units_generated.rs"));
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "f64", feature = "prefixes-common"))]
+    #[test]
+    fn gram_is_a_thousandth_of_the_kilogram() {
+        use super::constants::*;
+
+        assert_eq!(1000.0 * g, 1.0 * kg);
+        assert_eq!((1.0_f64 * kg).to(g).value(), 1000.0);
+        assert_eq!((1.0_f64 * mg).to(kg).value(), 1e-6);
+        assert_ne!(1.0 * g, 1.0 * kg);
+    }
+
+    #[cfg(all(feature = "f64", feature = "thermal"))]
+    #[test]
+    fn offsets_only_apply_to_absolute_values() {
+        use super::constants::*;
+
+        // converted and compared as absolute temperatures
+        assert_eq!((20.0_f64 * degC).to(K).value(), 293.15);
+        assert_eq!(20.0 * degC, 293.15 * K);
+
+        // added to and subtracted from as temperature differences
+        assert_eq!(20.0 * degC + 5.0 * K, 298.15 * K);
+        assert_eq!(20.0 * degC + 5.0 * degC, 298.15 * K);
+        assert_eq!(20.0 * degC + 20.0 * degC, 313.15 * K);
+        assert_eq!(30.0 * degC - 10.0 * degC, 293.15 * K);
+
+        // multiplied and divided as temperature differences
+        assert_eq!((20.0_f64 * degC * (1.0_f64 * m)).value(), 20.0);
+        assert_eq!((20.0_f64 * degC * m).to(K * m).value(), 20.0);
+        assert_eq!((20.0_f64 * degC / (2.0_f64 * s)).value(), 10.0);
+        assert_eq!((1.0_f64 / (20.0_f64 * degC)).value(), 0.05);
+    }
+
+    #[cfg(all(feature = "f64", feature = "ext-methods"))]
+    #[test]
+    fn ext_methods_default_to_f64() {
+        use super::constants::*;
+        use crate::f64::QuantityExt;
+
+        let d = 2.0.km();
+        assert_eq!((d * 2.0).to(m).value(), 4000.0);
+    }
+
+    #[cfg(all(feature = "f32", feature = "ext-methods"))]
+    #[test]
+    fn ext_methods_infer_f32_from_their_use() {
+        use super::constants::*;
+        use crate::f32::QuantityExt;
+
+        let d = 2.0.km();
+        assert_eq!((d * 2.0).to(m).value(), 4000.0_f32);
+    }
+
+    #[cfg(all(feature = "f64", feature = "ext-methods"))]
+    #[test]
+    fn generic_ext_methods_work_for_any_scalar() {
+        use super::constants::*;
+        use crate::{QuantityExt, ScalarExt};
+
+        let d: crate::Quantity<f64, _> = 2.0.km();
+        assert_eq!(d.to(m).value(), 2000.0);
+        assert_eq!(2.0_f64.km(), 2.0_f64.of(km));
+    }
+}
@@ -1,6 +1,10 @@
 use super::Unit;
-use crate::{dimension::Dimensions, quantity::Quantity};
-use core::ops::Mul;
+use crate::{
+    dimension::{CanDivideBy, CanMultiplyWith, Dimensions},
+    quantity::Quantity,
+    scalar::Scalar,
+};
+use core::ops::{Div, Mul};
 
 #[cfg(feature = "f32")]
 impl<D: Dimensions> Mul<Unit<D>> for f32 {
@@ -19,3 +23,85 @@ impl<D: Dimensions> Mul<Unit<D>> for f64 {
         Quantity::with_unit(self, unit)
     }
 }
+
+/// Multiplication of a quantity by a unit, keeping the value.
+///
+/// # Examples
+///
+/// ```
+/// # use danwi::prelude::*;
+/// # use danwi::f64::constants::{kW, kg, km, m, ms, s};
+///
+/// let v = 3.0 * m / s;
+/// assert_eq!(v, 3.0.mps());
+///
+/// let rate = 680.0 * kg / s;
+/// let thrust = rate * 3.6 * km / s;
+/// assert_eq!(thrust, 2448.0.kN());
+///
+/// let energy = 2.0 * kW * ms;
+/// assert_eq!(energy, 2.0.J());
+/// ```
+impl<S, D1, D2> Mul<Unit<D2>> for Quantity<S, D1>
+where
+    S: Scalar,
+    D1: CanMultiplyWith<D2>,
+    D2: Dimensions,
+{
+    type Output = Quantity<S, <D1 as CanMultiplyWith<D2>>::Output>;
+
+    fn mul(self, unit: Unit<D2>) -> Self::Output {
+        Quantity::with_unit(self.value, self.unit.times(unit))
+    }
+}
+
+impl<S, D1, D2> Div<Unit<D2>> for Quantity<S, D1>
+where
+    S: Scalar,
+    D1: CanDivideBy<D2>,
+    D2: Dimensions,
+{
+    type Output = Quantity<S, <D1 as CanDivideBy<D2>>::Output>;
+
+    fn div(self, unit: Unit<D2>) -> Self::Output {
+        Quantity::with_unit(self.value, self.unit.per(unit))
+    }
+}
+
+/// Product of two units, combining their prefixes.
+///
+/// # Examples
+///
+/// ```
+/// # use danwi::prelude::*;
+/// # use danwi::f64::constants::{kN, km, m, ms};
+///
+/// let torque = 5.0 * (kN * m);
+/// assert_eq!(torque, 5.0.kJ());
+///
+/// let speed = 72.0 * (km / ms);
+/// assert_eq!(speed, 72e6.mps());
+/// ```
+impl<D1, D2> Mul<Unit<D2>> for Unit<D1>
+where
+    D1: CanMultiplyWith<D2>,
+    D2: Dimensions,
+{
+    type Output = Unit<<D1 as CanMultiplyWith<D2>>::Output>;
+
+    fn mul(self, rhs: Unit<D2>) -> Self::Output {
+        self.times(rhs)
+    }
+}
+
+impl<D1, D2> Div<Unit<D2>> for Unit<D1>
+where
+    D1: CanDivideBy<D2>,
+    D2: Dimensions,
+{
+    type Output = Unit<<D1 as CanDivideBy<D2>>::Output>;
+
+    fn div(self, rhs: Unit<D2>) -> Self::Output {
+        self.per(rhs)
+    }
+}
@@ -1,3 +1,9 @@
+//! Decimal SI prefixes and binary IEC prefixes.
+//!
+//! Decimal prefixes are powers of ten, added to the prefix of a unit, while
+//! binary prefixes are factors, which are powers of two and therefore exact
+//! in floating point, so that e.g. `KiB` converts exactly to `kB`.
+
 pub const QUETTA: i8 = 30;
 pub const RONNA: i8 = 27;
 pub const YOTTA: i8 = 24;
@@ -22,3 +28,158 @@ pub const ZEPTO: i8 = -21;
 pub const YOCTO: i8 = -24;
 pub const RONTO: i8 = -27;
 pub const QUECTO: i8 = -30;
+
+pub const KIBI: f64 = 1024.0;
+pub const MEBI: f64 = 1048576.0;
+pub const GIBI: f64 = 1073741824.0;
+pub const TEBI: f64 = 1099511627776.0;
+pub const PEBI: f64 = 1125899906842624.0;
+pub const EXBI: f64 = 1152921504606846976.0;
+
+/// A decimal SI prefix or a binary IEC prefix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Prefix {
+    name: &'static str,
+    symbol: &'static str,
+    aliases: &'static [&'static str],
+    exponent: i8,
+    factor: f64,
+}
+
+impl Prefix {
+    pub const fn new(name: &'static str, symbol: &'static str, exponent: i8) -> Self {
+        Self {
+            name,
+            symbol,
+            aliases: &[],
+            exponent,
+            factor: 1.0,
+        }
+    }
+
+    /// Returns a binary prefix standing for `factor` (e.g. [`KIBI`]).
+    pub const fn binary(name: &'static str, symbol: &'static str, factor: f64) -> Self {
+        Self {
+            factor,
+            ..Self::new(name, symbol, 0)
+        }
+    }
+
+    /// Returns the prefix with alternative symbols (e.g. `μ` for micro).
+    pub const fn with_aliases(self, aliases: &'static [&'static str]) -> Self {
+        Self { aliases, ..self }
+    }
+
+    /// Returns the name of the prefix (e.g. `kilo`).
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the symbol of the prefix (e.g. `k`, or `u` for micro).
+    #[inline]
+    pub const fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    /// Returns the alternative symbols of the prefix (e.g. `μ` and `µ` for
+    /// micro).
+    #[inline]
+    pub const fn aliases(&self) -> &'static [&'static str] {
+        self.aliases
+    }
+
+    /// Returns the power of ten the prefix stands for, `0` for binary
+    /// prefixes.
+    #[inline]
+    pub const fn exponent(&self) -> i8 {
+        self.exponent
+    }
+
+    /// Returns the factor a binary prefix stands for (e.g. `1024.0` for kibi),
+    /// `1.0` for decimal prefixes.
+    #[inline]
+    pub const fn factor(&self) -> f64 {
+        self.factor
+    }
+}
+
+/// All SI prefixes, from largest to smallest.
+pub const ALL: &[Prefix] = &[
+    Prefix::new("quetta", "Q", QUETTA),
+    Prefix::new("ronna", "R", RONNA),
+    Prefix::new("yotta", "Y", YOTTA),
+    Prefix::new("zetta", "Z", ZETTA),
+    Prefix::new("exa", "E", EXA),
+    Prefix::new("peta", "P", PETA),
+    Prefix::new("tera", "T", TERA),
+    Prefix::new("giga", "G", GIGA),
+    Prefix::new("mega", "M", MEGA),
+    Prefix::new("kilo", "k", KILO),
+    Prefix::new("hecto", "h", HECTO),
+    Prefix::new("deca", "da", DECA),
+    Prefix::new("deci", "d", DECI),
+    Prefix::new("centi", "c", CENTI),
+    Prefix::new("milli", "m", MILLI),
+    Prefix::new("micro", "u", MICRO).with_aliases(&["μ", "µ"]),
+    Prefix::new("nano", "n", NANO),
+    Prefix::new("pico", "p", PICO),
+    Prefix::new("femto", "f", FEMTO),
+    Prefix::new("atto", "a", ATTO),
+    Prefix::new("zepto", "z", ZEPTO),
+    Prefix::new("yocto", "y", YOCTO),
+    Prefix::new("ronto", "r", RONTO),
+    Prefix::new("quecto", "q", QUECTO),
+];
+
+/// The most used SI prefixes, from largest to smallest.
+pub const COMMON: &[Prefix] = &[
+    Prefix::new("giga", "G", GIGA),
+    Prefix::new("mega", "M", MEGA),
+    Prefix::new("kilo", "k", KILO),
+    Prefix::new("milli", "m", MILLI),
+    Prefix::new("micro", "u", MICRO).with_aliases(&["μ", "µ"]),
+    Prefix::new("nano", "n", NANO),
+    Prefix::new("pico", "p", PICO),
+];
+
+/// The binary IEC prefixes, from largest to smallest, for units of
+/// information.
+///
+/// # Examples
+///
+/// ```
+/// # use danwi::prelude::*;
+/// # use danwi::f64::constants::{KiB, kB};
+/// assert_eq!(1.0.KiB(), 1024.0.B());
+/// assert_eq!(1.0.KiB().to(kB).value(), 1.024);
+/// assert_eq!(1000.0.kB().to(KiB).value(), 976.5625);
+/// assert_eq!(1.0.GiB(), 1024.0.MiB());
+/// assert_eq!(1.0.MiB(), 8.0.Mib());
+/// ```
+pub const BINARY: &[Prefix] = &[
+    Prefix::binary("exbi", "Ei", EXBI),
+    Prefix::binary("pebi", "Pi", PEBI),
+    Prefix::binary("tebi", "Ti", TEBI),
+    Prefix::binary("gibi", "Gi", GIBI),
+    Prefix::binary("mebi", "Mi", MEBI),
+    Prefix::binary("kibi", "Ki", KIBI),
+];
+
+/// No prefixes, for units such as the minute.
+pub const NONE: &[Prefix] = &[];
+
+/// The prefixes of units without a prefix policy, selected by the
+/// `prefixes-all` and `prefixes-common` features.
+#[cfg(feature = "prefixes-all")]
+pub const DEFAULT: &[Prefix] = ALL;
+
+/// The prefixes of units without a prefix policy, selected by the
+/// `prefixes-all` and `prefixes-common` features.
+#[cfg(all(feature = "prefixes-common", not(feature = "prefixes-all")))]
+pub const DEFAULT: &[Prefix] = COMMON;
+
+/// The prefixes of units without a prefix policy, selected by the
+/// `prefixes-all` and `prefixes-common` features.
+#[cfg(not(any(feature = "prefixes-common", feature = "prefixes-all")))]
+pub const DEFAULT: &[Prefix] = NONE;
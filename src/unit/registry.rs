@@ -0,0 +1,297 @@
+//! Runtime registry of the units defined with `define_units!`.
+
+use super::prefix::Prefix;
+use crate::dimension::{DimensionInfo, Dimensions};
+
+/// Runtime description of a unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitInfo {
+    name: &'static str,
+    symbol: &'static str,
+    names: &'static [&'static str],
+    symbols: &'static [&'static str],
+    dimension: DimensionInfo,
+    prefixes: &'static [Prefix],
+    binary_prefixes: &'static [Prefix],
+    scale: i8,
+    factor: f64,
+    offset: f64,
+    power: i8,
+    kind: Option<&'static str>,
+    display: Option<&'static str>,
+}
+
+impl UnitInfo {
+    pub const fn new(
+        name: &'static str,
+        symbol: &'static str,
+        dimension: DimensionInfo,
+        prefixes: &'static [Prefix],
+        scale: i8,
+        power: i8,
+    ) -> Self {
+        Self {
+            name,
+            symbol,
+            names: &[],
+            symbols: &[],
+            dimension,
+            prefixes,
+            binary_prefixes: &[],
+            scale,
+            factor: 1.0,
+            offset: 0.0,
+            power,
+            kind: None,
+            display: None,
+        }
+    }
+
+    /// Returns the unit with alternative names, such as plurals and other
+    /// spellings (e.g. `meters` and `metre`).
+    pub const fn with_names(self, names: &'static [&'static str]) -> Self {
+        Self { names, ..self }
+    }
+
+    /// Returns the unit with alternative symbols (e.g. `Ω`).
+    pub const fn with_symbols(self, symbols: &'static [&'static str]) -> Self {
+        Self { symbols, ..self }
+    }
+
+    /// Returns the unit with binary prefixes besides its decimal ones (e.g.
+    /// [`prefix::BINARY`](super::prefix::BINARY) for the byte).
+    pub const fn with_binary_prefixes(self, binary_prefixes: &'static [Prefix]) -> Self {
+        Self {
+            binary_prefixes,
+            ..self
+        }
+    }
+
+    /// Returns the unit with a factor besides its power of ten (e.g. `60.0`
+    /// for the minute).
+    pub const fn with_factor(self, factor: f64) -> Self {
+        Self { factor, ..self }
+    }
+
+    /// Returns the unit with the value of its zero in coherent units (e.g.
+    /// `273.15` for the degree Celsius).
+    pub const fn with_offset(self, offset: f64) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Returns the unit with the kind of quantity it measures (e.g.
+    /// `AbsorbedDose` for the gray).
+    pub const fn with_kind(self, kind: &'static str) -> Self {
+        Self {
+            kind: Some(kind),
+            ..self
+        }
+    }
+
+    /// Returns the unit with the symbol shown in results (e.g. `m/s`).
+    pub const fn with_display(self, display: &'static str) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+
+    /// Returns the name of the unit (e.g. `meter_per_second`).
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the symbol of the unit (e.g. `mps`).
+    #[inline]
+    pub const fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    /// Returns the symbol shown in results, which is the symbol of the unit
+    /// unless it is only an identifier (e.g. `m/s` for `mps`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use danwi::unit::REGISTRY;
+    ///
+    /// assert_eq!(REGISTRY.by_symbol("V").unwrap().display_symbol(), "V");
+    /// # #[cfg(feature = "mechanical")]
+    /// assert_eq!(REGISTRY.by_symbol("kgmps").unwrap().display_symbol(), "kg·m/s");
+    /// ```
+    #[inline]
+    pub const fn display_symbol(&self) -> &'static str {
+        match self.display {
+            Some(display) => display,
+            None => self.symbol,
+        }
+    }
+
+    /// Returns the alternative names of the unit.
+    #[inline]
+    pub const fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+
+    /// Returns the alternative symbols of the unit.
+    #[inline]
+    pub const fn symbols(&self) -> &'static [&'static str] {
+        self.symbols
+    }
+
+    /// Returns the dimension of the unit.
+    #[inline]
+    pub const fn dimension(&self) -> DimensionInfo {
+        self.dimension
+    }
+
+    /// Returns the decimal prefixes the unit can be combined with.
+    #[inline]
+    pub const fn prefixes(&self) -> &'static [Prefix] {
+        self.prefixes
+    }
+
+    /// Returns the binary prefixes the unit can be combined with.
+    #[inline]
+    pub const fn binary_prefixes(&self) -> &'static [Prefix] {
+        self.binary_prefixes
+    }
+
+    /// Returns the power of ten of the unit relative to the coherent SI unit
+    /// of its dimension (e.g. `-3` for the gram).
+    #[inline]
+    pub const fn scale(&self) -> i8 {
+        self.scale
+    }
+
+    /// Returns the factor of the unit besides its power of ten (e.g. `60.0`
+    /// for the minute).
+    #[inline]
+    pub const fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Returns the value of the zero of the unit in coherent units (e.g.
+    /// `273.15` for the degree Celsius).
+    #[inline]
+    pub const fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Returns whether the unit is the coherent SI unit of its dimension.
+    #[inline]
+    pub const fn is_coherent(&self) -> bool {
+        self.scale == 0 && self.factor == 1.0 && self.offset == 0.0 && self.power == 1
+    }
+
+    /// Returns the kind of quantity the unit measures if its dimension is
+    /// shared by several kinds, such as `Activity` for the becquerel and
+    /// `Frequency` for the hertz.
+    ///
+    /// Quantities of these kinds have the same type, so that e.g.
+    /// `1.0.Gy() + 1.0.Sv()` compiles, but a
+    /// [`DynQuantity`](crate::DynQuantity) carries the kind of its unit, and
+    /// neither it nor the [calculator](crate::dynamic::eval) adds or converts
+    /// quantities of different kinds into each other, nor shows other
+    /// quantities in their units. Quantities without a kind, such as those in
+    /// `J/kg` or `1/s`, go with any kind of their dimension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use danwi::unit::REGISTRY;
+    ///
+    /// assert_eq!(REGISTRY.by_symbol("Gy").unwrap().kind(), Some("AbsorbedDose"));
+    /// assert_eq!(REGISTRY.by_symbol("Sv").unwrap().kind(), Some("EquivalentDose"));
+    /// assert_eq!(REGISTRY.by_symbol("Hz").unwrap().kind(), Some("Frequency"));
+    /// assert_eq!(REGISTRY.by_symbol("J").unwrap().kind(), None);
+    /// ```
+    #[inline]
+    pub const fn kind(&self) -> Option<&'static str> {
+        self.kind
+    }
+
+    /// Returns the power a prefix is raised to together with the unit (e.g.
+    /// `2` for `m2`, so that `cm2` is 10⁻⁴ m², or `-1` for `per_s`).
+    #[inline]
+    pub const fn power(&self) -> i8 {
+        self.power
+    }
+}
+
+/// A table of units that can be searched at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use danwi::{dimension::Pressure, unit::REGISTRY};
+///
+/// let newton = REGISTRY.by_symbol("N").unwrap();
+/// assert_eq!(newton.name(), "newton");
+/// assert_eq!(newton.dimension().name(), Some("Force"));
+///
+/// assert_eq!(REGISTRY.by_name("pascal"), REGISTRY.by_symbol("Pa"));
+///
+/// let mut pressure = REGISTRY.by_dimension::<Pressure>();
+/// assert_eq!(pressure.next().map(|unit| unit.symbol()), Some("Pa"));
+/// assert_eq!(pressure.next(), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Registry {
+    units: &'static [UnitInfo],
+}
+
+impl Registry {
+    pub const fn new(units: &'static [UnitInfo]) -> Self {
+        Self { units }
+    }
+
+    /// Returns all units in the order they were defined.
+    #[inline]
+    pub const fn units(&self) -> &'static [UnitInfo] {
+        self.units
+    }
+
+    /// Returns an iterator over all units.
+    pub fn iter(&self) -> impl Iterator<Item = &'static UnitInfo> + use<> {
+        self.units.iter()
+    }
+
+    /// Finds a unit by its symbol or an alternative one (without prefix).
+    pub fn by_symbol(&self, symbol: &str) -> Option<&'static UnitInfo> {
+        self.iter()
+            .find(|unit| unit.symbol == symbol || unit.symbols.contains(&symbol))
+    }
+
+    /// Finds a unit by its name or an alternative one (without prefix).
+    pub fn by_name(&self, name: &str) -> Option<&'static UnitInfo> {
+        self.iter()
+            .find(|unit| unit.name == name || unit.names.contains(&name))
+    }
+
+    /// Returns all units of the dimension `D`.
+    pub fn by_dimension<D: Dimensions>(&self) -> impl Iterator<Item = &'static UnitInfo> + use<D> {
+        self.iter()
+            .filter(|unit| unit.dimension == DimensionInfo::of::<D>())
+    }
+
+    /// Returns all units whose dimension has the given exponents of the SI
+    /// base dimensions and no additional ones (e.g. not the bit).
+    pub fn by_exponents(
+        &self,
+        exponents: [i8; 7],
+    ) -> impl Iterator<Item = &'static UnitInfo> + use<> {
+        let dimension = DimensionInfo::from_exponents(exponents);
+        self.iter().filter(move |unit| unit.dimension == dimension)
+    }
+}
+
+impl IntoIterator for Registry {
+    type Item = &'static UnitInfo;
+    type IntoIter = core::slice::Iter<'static, UnitInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.units.iter()
+    }
+}
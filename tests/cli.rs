@@ -0,0 +1,100 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn danwi(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_danwi"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs the calculator and returns its output, asserting that it succeeded.
+fn stdout(args: &[&str]) -> String {
+    let output = danwi(args);
+    assert!(output.status.success(), "{:?}: {:?}", args, output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Runs the calculator and returns its errors, asserting that it failed.
+fn stderr(args: &[&str]) -> String {
+    let output = danwi(args);
+    assert!(!output.status.success(), "{:?}: {:?}", args, output);
+    String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn results_are_shown_in_coherent_units() {
+    assert_eq!(stdout(&["5 mA * 2 kOhm"]), "10 V\n");
+    assert_eq!(stdout(&["72 km/h"]), "20 m/s\n");
+    assert_eq!(stdout(&["1 MiB"]), "8388608 b\n");
+    assert_eq!(stdout(&["2", "Gy", "*", "3"]), "6 Gy\n");
+}
+
+#[test]
+fn results_are_converted_to_the_target_unit() {
+    assert_eq!(stdout(&["10.5mV to uV"]), "10500 uV\n");
+    assert_eq!(stdout(&["10.5mV", "--to", "uV"]), "10500 uV\n");
+    assert_eq!(stdout(&["1 KiB", "-t", "kB"]), "1.024 kB\n");
+    assert_eq!(stdout(&["1 Gy to J/kg"]), "1 J/kg\n");
+}
+
+#[test]
+fn offset_units_convert_absolute_values() {
+    assert_eq!(stdout(&["300 K to degC"]), "26.85 degC\n");
+    assert_eq!(stdout(&["20 degC"]), "293.15 K\n");
+    assert_eq!(stdout(&["20 degC + 5 degC"]), "298.15 K\n");
+    assert_eq!(stdout(&["20 degC + 5 K to degC"]), "25 degC\n");
+}
+
+#[test]
+fn errors_underline_their_spans() {
+    assert_eq!(
+        stderr(&["3 V + 2 A"]),
+        "error: cannot add or subtract Voltage (M·L²·T⁻³·I⁻¹) and ElectricCurrent (I)\n  \
+         3 V + 2 A\n  ^^^   ^^^\n"
+    );
+    assert_eq!(
+        stderr(&["(m^100)^2"]),
+        "error: exponent of the dimension is out of range\n  (m^100)^2\n  ^^^^^^^^^\n"
+    );
+    assert_eq!(
+        stderr(&["1 V to furlong"]),
+        "error: unknown unit\n  furlong\n  ^^^^^^^\n"
+    );
+    assert_eq!(
+        stderr(&["1 Gy to Sv"]),
+        "error: cannot convert AbsorbedDose (L²·T⁻²) to EquivalentDose (L²·T⁻²)\n"
+    );
+    assert_eq!(
+        stderr(&["1 Hz to Bq"]),
+        "error: cannot convert Frequency (T⁻¹) to Activity (T⁻¹)\n"
+    );
+}
+
+#[test]
+fn standard_input_is_read_line_by_line() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_danwi"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"1 km to m\n\n3 furlongs\n2 s * 3\n")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1000 m\n6 s\n");
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("error: unknown unit")
+    );
+}
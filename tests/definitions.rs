@@ -0,0 +1,208 @@
+use std::{fs, path::Path, process::Command};
+
+/// Builds the crate with each `(variable, name, contents)` file written to
+/// `name` and passed in the environment variable `variable`, returning the
+/// warnings or the errors of Cargo.
+fn build_with(files: &[(&str, &str, &str)]) -> Result<String, String> {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .args(["check", "--offline", "--lib", "--no-default-features"])
+        .args(["--features", "f64,ext-methods"])
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(tmp.join("definitions"));
+
+    for (variable, name, contents) in files {
+        let path = tmp.join(name);
+        fs::write(&path, contents).unwrap();
+        command.env(variable, path);
+    }
+
+    let output = command.output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    match output.status.success() {
+        true => Ok(stderr),
+        false => Err(stderr),
+    }
+}
+
+/// Builds the crate with `units` merged after the built-in units.
+fn build_with_units(name: &str, units: &str) -> Result<String, String> {
+    build_with(&[("DANWI_EXTRA_UNITS", name, units)])
+}
+
+#[test]
+fn invalid_scales_are_errors_at_their_lines() {
+    let stderr = build_with_units(
+        "invalid_scales.txt",
+        "\
+zero_unit   zu   length   scale=0
+minus_unit  mu   length   scale=-3
+huge_unit   hu   length   scale=1e200
+inf_unit    iu   length   scale=inf
+",
+    )
+    .unwrap_err();
+
+    for (line, message) in [
+        (1, "scale `0` is not a positive finite number: zero_unit"),
+        (2, "scale `-3` is not a positive finite number: minus_unit"),
+        (
+            3,
+            "scale `1e200` is out of range (1e-128 to 1e127): huge_unit",
+        ),
+        (4, "scale `inf` is not a positive finite number: inf_unit"),
+    ] {
+        let expected = format!("invalid_scales.txt:{}: {}", line, message);
+        assert!(
+            stderr.contains(&expected),
+            "{} not in:\n{}",
+            expected,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn valid_scales_are_accepted() {
+    build_with_units(
+        "valid_scales.txt",
+        "\
+tiny_unit   tu   length   scale=1e-100
+odd_unit    ou   length   scale=0.3048
+",
+    )
+    .unwrap();
+}
+
+#[test]
+fn definition_files_can_be_replaced_and_extended() {
+    let stderr = build_with(&[(
+        "DANWI_UNITS",
+        "replaced_units.txt",
+        "\
+second  s  time
+meter   m  length  prefixes=all
+gram    g  mass    scale=1e-3
+",
+    )])
+    .unwrap();
+    assert!(!stderr.contains("warning"), "{}", stderr);
+
+    let stderr = build_with(&[
+        (
+            "DANWI_EXTRA_DIMENSIONS",
+            "extra_dimensions.txt",
+            "linear_density:  0 -1 1 0 0 0 0  # Linear density (kg/m) = M·L⁻¹\n",
+        ),
+        (
+            "DANWI_EXTRA_UNITS",
+            "extra_units.txt",
+            "denier  den  linear_density  scale=1.111e-7 plural=deniers prefixes=none\n",
+        ),
+    ])
+    .unwrap();
+    assert!(!stderr.contains("warning"), "{}", stderr);
+}
+
+#[test]
+fn dimensions_may_have_any_exponent() {
+    let stderr = build_with(&[
+        (
+            "DANWI_EXTRA_DIMENSIONS",
+            "large_exponents.txt",
+            "\
+hyperjerk:  -6 1 0 0 0 0 0  # Sixth derivative of position = L·T⁻⁶
+hypervolume:  0 12 0 0 0 0 0
+",
+        ),
+        (
+            "DANWI_EXTRA_UNITS",
+            "large_exponents_units.txt",
+            "\
+meter_per_second_to_the_sixth  mps6  hyperjerk    prefixes=none
+meter_to_the_twelfth           m12   hypervolume  prefixes=none
+",
+        ),
+    ])
+    .unwrap();
+    assert!(!stderr.contains("warning"), "{}", stderr);
+}
+
+#[test]
+fn names_are_not_prefixed() {
+    // `kilometer` would clash with the meter and the kilo prefix if the
+    // names had prefixed methods
+    let stderr = build_with(&[(
+        "DANWI_UNITS",
+        "unprefixed_names.txt",
+        "\
+second     s     time
+meter      m     length  prefixes=all
+kilometer  kmtr  length  scale=1e3 prefixes=none
+",
+    )])
+    .unwrap();
+    assert!(!stderr.contains("warning"), "{}", stderr);
+}
+
+#[test]
+fn powers_may_have_any_exponent_in_range() {
+    let stderr = build_with(&[(
+        "DANWI_UNITS",
+        "large_powers.txt",
+        "\
+second                   s         time
+meter                    m         length  prefixes=common
+per_second_to_the_128th  per_s128  s^-128
+second_to_the_127th      s127      s^127
+meter_to_the_fourth      m4        m^4
+",
+    )])
+    .unwrap();
+    assert!(!stderr.contains("warning"), "{}", stderr);
+}
+
+#[test]
+fn powers_out_of_range_are_errors_at_their_lines() {
+    let stderr = build_with(&[(
+        "DANWI_UNITS",
+        "invalid_powers.txt",
+        "\
+second                       s          time
+meter                        m          length  prefixes=all
+kilometer                    km         length  scale=1e3 prefixes=none
+second_to_the_200th          s200       s^200
+meter_to_the_fifth           m5         m^5
+per_kilometer_to_the_43rd    per_km43   km^-43
+",
+    )])
+    .unwrap_err();
+
+    for (line, message) in [
+        (
+            4,
+            "exponent 200 is out of range (-128..=127): second_to_the_200th",
+        ),
+        (
+            6,
+            "exponent -43 of `per_kilometer_to_the_43rd` is out of range, `km` would be \
+             scaled by more than 1e-128 to 1e127",
+        ),
+        (
+            5,
+            "exponent 5 of `meter_to_the_fifth` is out of range, `Qm` would be scaled by \
+             more than 1e-128 to 1e127",
+        ),
+    ] {
+        let expected = format!("invalid_powers.txt:{}: {}", line, message);
+        assert!(
+            stderr.contains(&expected),
+            "{} not in:\n{}",
+            expected,
+            stderr
+        );
+    }
+}
@@ -0,0 +1,5 @@
+use danwi::f64::QuantityExt;
+
+fn main() {
+    let _ = 3.0.V() + 2.0.A();
+}
@@ -0,0 +1,11 @@
+use danwi::{Unit, dimension::ExtraDimension};
+use typenum::{P1, Z0, tarr};
+
+type Bits = ExtraDimension<tarr![P1]>;
+type Pixels = ExtraDimension<tarr![Z0, P1]>;
+
+fn main() {
+    let bits = 8.0 * Unit::<Bits>::base();
+    let pixels = 8.0 * Unit::<Pixels>::base();
+    let _ = bits + pixels;
+}
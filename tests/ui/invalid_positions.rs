@@ -0,0 +1,14 @@
+mod imaging {
+    danwi::define_dimensions! {
+        Pixels = 2,
+    }
+}
+
+mod finance {
+    danwi::define_dimensions! {
+        Currency = 2,
+        Shares = 9,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use danwi::f64::QuantityExt;
+
+fn main() {
+    let _ = 2.0.N() * 3.0.s() + 1.0.J();
+    let _ = 3.0.Pa() - 1.0.N() / 2.0.m();
+
+    // dimensions without a name are shown by their exponents
+    let _ = 1.0.V() * 1.0.m() + 1.0.V();
+}
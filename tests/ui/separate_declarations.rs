@@ -0,0 +1,19 @@
+mod imaging {
+    danwi::define_dimensions! {
+        Pixels = 2,
+    }
+}
+
+mod finance {
+    danwi::define_dimensions! {
+        Currency = 3,
+    }
+}
+
+use danwi::Unit;
+
+fn main() {
+    let pixels = 5.0 * Unit::<imaging::Pixels>::base();
+    let money = 5.0 * Unit::<finance::Currency>::base();
+    let _ = pixels + money;
+}
@@ -0,0 +1,5 @@
+use danwi::f64::QuantityExt;
+
+fn main() {
+    let _ = 10.0.m() - 2.0.s();
+}
@@ -0,0 +1,5 @@
+use danwi::q;
+
+fn main() {
+    let _ = q!(4.7 furlongs);
+}